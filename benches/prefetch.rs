@@ -0,0 +1,53 @@
+//! Compares `for_each` and `for_each_prefetch` on a long list
+//! whose nodes are linked in a shuffled order, so that walking
+//! the list jumps around the heap.
+//!
+//! Run with `cargo bench --bench prefetch`.
+use cdlist::LinkNode;
+use std::{hint::black_box, time::Instant};
+
+const LEN: usize = 100_000;
+const ROUNDS: u32 = 50;
+
+fn main() {
+    let mut nodes = (0..LEN).map(LinkNode::new).collect::<Vec<_>>();
+    shuffle(&mut nodes);
+    let (first, rest) = nodes.split_first_mut().unwrap();
+    for node in rest.iter_mut() {
+        first.add_before(node);
+    }
+
+    let plain = measure(|| {
+        let mut sum = 0;
+        first.for_each(|&i| sum += i);
+        sum
+    });
+    let prefetch = measure(|| {
+        let mut sum = 0;
+        first.for_each_prefetch(|&i| sum += i);
+        sum
+    });
+    println!("for_each:          {plain:>10.3?} per walk");
+    println!("for_each_prefetch: {prefetch:>10.3?} per walk");
+}
+
+fn measure<F: FnMut() -> usize>(mut walk: F) -> std::time::Duration {
+    black_box(walk());
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        black_box(walk());
+    }
+    start.elapsed() / ROUNDS
+}
+
+/// Fisher-Yates shuffle driven by a xorshift generator,
+/// to avoid depending on a random number crate.
+fn shuffle<T>(slice: &mut [T]) {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    for i in (1..slice.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        slice.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+}
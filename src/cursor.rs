@@ -0,0 +1,143 @@
+//! A cursor for walking and modifying a list at the same time.
+use crate::{LinkNode, ListHead};
+use core::{marker::PhantomData, ptr::NonNull};
+
+/// A cursor over a list, which can remove nodes from
+/// and insert nodes into the list while walking it.
+///
+/// The cursor starts at the node it is created from, called the anchor.
+/// Besides pointing at a node, it can point at the end position
+/// between the last node (the one before the anchor) and the anchor,
+/// where [`Cursor::current`] returns `None`.
+/// This makes a single pass over the list easy to express:
+///
+/// ```
+/// use cdlist::LinkNode;
+///
+/// let mut node0 = LinkNode::new(0);
+/// let mut node1 = LinkNode::new(1);
+/// let mut node2 = LinkNode::new(2);
+/// node0.add(&mut node2);
+/// node0.add(&mut node1);
+///
+/// // the other nodes are left alone while `cursor` is alive
+/// let mut cursor = unsafe { node0.cursor_mut() };
+/// while let Some(data) = cursor.current() {
+///     if *data % 2 == 1 {
+///         cursor.remove_current();
+///     } else {
+///         cursor.move_next();
+///     }
+/// }
+/// assert_eq!(node0.to_vec(), vec![0, 2]);
+/// assert!(node1.is_standalone());
+/// ```
+///
+/// Created by [`LinkNode::cursor_mut`].
+pub struct Cursor<'a, T> {
+    anchor: NonNull<ListHead<T>>,
+    current: Option<NonNull<ListHead<T>>>,
+    marker: PhantomData<&'a mut LinkNode<T>>,
+}
+
+impl<'a, T> Cursor<'a, T> {
+    #[inline]
+    pub(crate) fn new(node: &'a mut LinkNode<T>) -> Self {
+        let anchor = node.head();
+        Self {
+            anchor,
+            current: Some(anchor),
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a mutable reference to the data of the node
+    /// the cursor points at, or `None` at the end position.
+    #[inline]
+    pub fn current(&mut self) -> Option<&mut T> {
+        self.current
+            .map(|this| unsafe { ListHead::data_ptr(this).as_mut() })
+    }
+
+    /// Moves the cursor to the next node.
+    /// Moving past the last node reaches the end position,
+    /// and moving past the end position wraps around to the anchor.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            None => Some(self.anchor),
+            Some(this) => {
+                let next = unsafe { ListHead::next_ptr(this) };
+                (next != self.anchor).then_some(next)
+            }
+        };
+    }
+
+    /// Moves the cursor to the previous node.
+    /// Moving back from the anchor reaches the end position,
+    /// and moving back from the end position wraps around to the last node.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            None => Some(unsafe { ListHead::prev_ptr(self.anchor) }),
+            Some(this) if this == self.anchor => None,
+            Some(this) => Some(unsafe { ListHead::prev_ptr(this) }),
+        };
+    }
+
+    /// Removes the node the cursor points at from the list,
+    /// turning it into a standalone element, and moves the cursor
+    /// to the following position. Returns `true` if a node was removed.
+    ///
+    /// If the anchor is removed, the node after it becomes the new anchor.
+    /// Nothing is removed at the end position, or if the current node
+    /// is the only node left in the list, in which case the cursor
+    /// just moves to the end position.
+    pub fn remove_current(&mut self) -> bool {
+        let Some(this) = self.current else {
+            return false;
+        };
+        let next = unsafe { ListHead::next_ptr(this) };
+        if next == this {
+            self.current = None;
+            return false;
+        }
+        if this == self.anchor {
+            self.anchor = next;
+            self.current = Some(next);
+        } else {
+            self.current = (next != self.anchor).then_some(next);
+        }
+        unsafe {
+            ListHead::delist(this);
+            ListHead::init_head(this);
+        }
+        true
+    }
+
+    /// Removes `other` from its current position in its list
+    /// and inserts it after the node the cursor points at.
+    /// At the end position, `other` is inserted before the anchor,
+    /// becoming the last node. The cursor does not move.
+    ///
+    /// Does nothing if `other` is the current node,
+    /// or the anchor at the end position, where it is already.
+    pub fn insert_after(&mut self, other: &mut LinkNode<T>) {
+        let other_list = other.head();
+        unsafe {
+            match self.current {
+                Some(this) if this == other_list => {}
+                Some(this) => {
+                    ListHead::delist(other_list);
+                    ListHead::add(this, other_list);
+                }
+                // the anchor can be reached after removing the first one
+                None if self.anchor == other_list => {}
+                None => {
+                    ListHead::delist(other_list);
+                    ListHead::add_before(self.anchor, other_list);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Detection of structural modifications of a list during a traversal,
+//! enabled by the `debug-guard` feature.
+//!
+//! Every node carries a generation counter, which is bumped
+//! whenever its links change. A traversal takes snapshots of
+//! the nodes it relies on and checks that their generations
+//! did not change after handing out data to user code.
+//! Without the feature, snapshots are zero-sized and checks are no-ops.
+use crate::ListHead;
+#[cfg(feature = "debug-guard")]
+use core::cell::Cell;
+use core::ptr::NonNull;
+
+/// The generation of a node recorded at some point of a traversal.
+pub(crate) struct Snapshot {
+    #[cfg(feature = "debug-guard")]
+    generation: NonNull<Cell<usize>>,
+    #[cfg(feature = "debug-guard")]
+    expected: usize,
+}
+
+impl Snapshot {
+    #[cfg(feature = "debug-guard")]
+    #[inline(always)]
+    pub(crate) fn new<T>(list: NonNull<ListHead<T>>) -> Self {
+        let generation = unsafe { NonNull::from(&(*list.as_ptr()).generation) };
+        Self {
+            generation,
+            expected: unsafe { generation.as_ref() }.get(),
+        }
+    }
+
+    #[cfg(not(feature = "debug-guard"))]
+    #[inline(always)]
+    pub(crate) fn new<T>(_list: NonNull<ListHead<T>>) -> Self {
+        Self {}
+    }
+
+    /// Panics if the links of the node changed since the snapshot.
+    #[cfg(feature = "debug-guard")]
+    #[inline(always)]
+    pub(crate) fn check(&self) {
+        assert_eq!(
+            unsafe { self.generation.as_ref() }.get(),
+            self.expected,
+            "list was structurally modified during traversal"
+        );
+    }
+
+    #[cfg(not(feature = "debug-guard"))]
+    #[inline(always)]
+    pub(crate) fn check(&self) {}
+}
@@ -0,0 +1,345 @@
+//! Iterators over the elements of a list.
+use crate::{guard::Snapshot, LinkNode, ListHead};
+use core::{
+    iter::{FusedIterator, Rev},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+/// An iterator over immutable references to the data
+/// of each element in a list, starting from a given node.
+///
+/// Created by [`LinkNode::iter`].
+///
+/// It is double-ended: `next_back` walks the list backwards from
+/// the node before the start, and the two ends never yield
+/// the same element twice.
+///
+/// ```
+/// use cdlist::LinkNode;
+///
+/// let mut node0 = LinkNode::new(0);
+/// let mut node1 = LinkNode::new(1);
+/// let mut node2 = LinkNode::new(2);
+/// node0.add(&mut node2);
+/// node0.add(&mut node1);
+///
+/// // no node is dropped or modified while `iter` is alive
+/// let mut iter = unsafe { node0.iter() };
+/// assert_eq!(iter.next_back(), Some(&2));
+/// assert_eq!(iter.next(), Some(&0));
+/// assert_eq!(iter.next_back(), Some(&1));
+/// assert_eq!(iter.next(), None);
+/// ```
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Iter<'a, T> {
+    raw: RawCursors<T>,
+    marker: PhantomData<&'a LinkNode<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    #[inline]
+    pub(crate) fn new(node: &'a LinkNode<T>) -> Self {
+        Self {
+            raw: unsafe { RawCursors::new(node.head()) },
+            marker: PhantomData,
+        }
+    }
+
+    /// `raw` must walk a list that outlives `'a`.
+    #[inline]
+    pub(crate) unsafe fn from_raw(raw: RawCursors<T>) -> Self {
+        Self {
+            raw,
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterates in reverse order starting from `node`,
+    /// i.e. in the order of `for_each_rev`.
+    #[inline]
+    pub(crate) fn new_rev(node: &'a LinkNode<T>) -> Rev<Self> {
+        Self {
+            raw: unsafe { RawCursors::new_rev(node.head()) },
+            marker: PhantomData,
+        }
+        .rev()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.raw
+            .next()
+            .map(|this| unsafe { ListHead::data_ptr(this).as_ref() })
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.raw
+            .next_back()
+            .map(|this| unsafe { ListHead::data_ptr(this).as_ref() })
+    }
+}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An iterator over immutable references to the data
+/// of each element in a list, which knows its exact length.
+///
+/// Created by [`LinkNode::iter_exact`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExactIter<'a, T> {
+    iter: Iter<'a, T>,
+    len: usize,
+}
+
+impl<'a, T> ExactIter<'a, T> {
+    #[inline]
+    pub(crate) fn new(iter: Iter<'a, T>, len: usize) -> Self {
+        Self { iter, len }
+    }
+}
+
+impl<'a, T> Iterator for ExactIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.iter.next();
+        if next.is_some() {
+            self.len -= 1;
+        }
+        next
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ExactIter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next = self.iter.next_back();
+        if next.is_some() {
+            self.len -= 1;
+        }
+        next
+    }
+}
+
+impl<T> ExactSizeIterator for ExactIter<'_, T> {}
+
+impl<T> FusedIterator for ExactIter<'_, T> {}
+
+/// An iterator over mutable references to the data
+/// of each element in a list, starting from a given node.
+///
+/// Created by [`LinkNode::iter_mut`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterMut<'a, T> {
+    raw: RawCursors<T>,
+    marker: PhantomData<&'a mut LinkNode<T>>,
+}
+
+impl<'a, T> IterMut<'a, T> {
+    #[inline]
+    pub(crate) fn new(node: &'a mut LinkNode<T>) -> Self {
+        Self {
+            raw: unsafe { RawCursors::new(node.head()) },
+            marker: PhantomData,
+        }
+    }
+
+    /// `raw` must walk a list that outlives `'a`,
+    /// and is not accessed by anything else during `'a`.
+    #[inline]
+    pub(crate) unsafe fn from_raw(raw: RawCursors<T>) -> Self {
+        Self {
+            raw,
+            marker: PhantomData,
+        }
+    }
+
+    /// Iterates in reverse order starting from `node`,
+    /// i.e. in the order of `for_each_rev_mut`.
+    #[inline]
+    pub(crate) fn new_rev(node: &'a mut LinkNode<T>) -> Rev<Self> {
+        Self {
+            raw: unsafe { RawCursors::new_rev(node.head()) },
+            marker: PhantomData,
+        }
+        .rev()
+    }
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // each node is yielded at most once,
+        // so the returned references never alias.
+        self.raw
+            .next()
+            .map(|this| unsafe { ListHead::data_ptr(this).as_mut() })
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.raw
+            .next_back()
+            .map(|this| unsafe { ListHead::data_ptr(this).as_mut() })
+    }
+}
+
+impl<T> FusedIterator for IterMut<'_, T> {}
+
+/// An iterator that walks a list round and round forever,
+/// yielding immutable references to the data of each element.
+///
+/// Created by [`LinkNode::cycle_iter`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct CycleIter<'a, T> {
+    current: NonNull<ListHead<T>>,
+    guard: Snapshot,
+    marker: PhantomData<&'a LinkNode<T>>,
+}
+
+impl<'a, T> CycleIter<'a, T> {
+    #[inline]
+    pub(crate) fn new(node: &'a LinkNode<T>) -> Self {
+        let current = node.head();
+        Self {
+            current,
+            guard: Snapshot::new(current),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for CycleIter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.guard.check();
+        let this = self.current;
+        self.current = unsafe { ListHead::next_ptr(this) };
+        self.guard = Snapshot::new(this);
+        Some(unsafe { ListHead::data_ptr(this).as_ref() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<T> FusedIterator for CycleIter<'_, T> {}
+
+/// Walks the list with a front and a back cursor,
+/// yielding pointers to each node exactly once.
+///
+/// The cursors start at the first node and the node before it,
+/// and the iteration ends once they have met.
+///
+/// Each cursor was derived from the links of a node, which is
+/// checked not to have changed under the `debug-guard` feature.
+pub(crate) struct RawCursors<T> {
+    front: NonNull<ListHead<T>>,
+    back: NonNull<ListHead<T>>,
+    front_guard: Snapshot,
+    back_guard: Snapshot,
+    done: bool,
+}
+
+impl<T> RawCursors<T> {
+    /// `start` must point to a valid list head,
+    /// and the list must not be modified during iteration.
+    #[inline(always)]
+    pub(crate) unsafe fn new(start: NonNull<ListHead<T>>) -> Self {
+        Self {
+            front: start,
+            back: ListHead::prev_ptr(start),
+            front_guard: Snapshot::new(start),
+            back_guard: Snapshot::new(start),
+            done: false,
+        }
+    }
+
+    /// Like `new`, but walks every node of the list except `sentinel`,
+    /// starting from the node after it. Yields nothing if `sentinel`
+    /// is the only node.
+    #[inline(always)]
+    pub(crate) unsafe fn excluding(sentinel: NonNull<ListHead<T>>) -> Self {
+        let front = ListHead::next_ptr(sentinel);
+        Self {
+            front,
+            back: ListHead::prev_ptr(sentinel),
+            front_guard: Snapshot::new(sentinel),
+            back_guard: Snapshot::new(sentinel),
+            done: front == sentinel,
+        }
+    }
+
+    /// Like `new`, but the cursors start at the node after `start`
+    /// and `start`, so that walking backwards begins at `start`.
+    #[inline(always)]
+    unsafe fn new_rev(start: NonNull<ListHead<T>>) -> Self {
+        Self {
+            front: ListHead::next_ptr(start),
+            back: start,
+            front_guard: Snapshot::new(start),
+            back_guard: Snapshot::new(start),
+            done: false,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn next(&mut self) -> Option<NonNull<ListHead<T>>> {
+        if self.done {
+            return None;
+        }
+        self.check();
+        let this = self.front;
+        if this == self.back {
+            self.done = true;
+        } else {
+            self.front = unsafe { ListHead::next_ptr(this) };
+            self.front_guard = Snapshot::new(this);
+        }
+        Some(this)
+    }
+
+    #[inline(always)]
+    pub(crate) fn next_back(&mut self) -> Option<NonNull<ListHead<T>>> {
+        if self.done {
+            return None;
+        }
+        self.check();
+        let this = self.back;
+        if this == self.front {
+            self.done = true;
+        } else {
+            self.back = unsafe { ListHead::prev_ptr(this) };
+            self.back_guard = Snapshot::new(this);
+        }
+        Some(this)
+    }
+
+    #[inline(always)]
+    fn check(&self) {
+        self.front_guard.check();
+        self.back_guard.check();
+    }
+}
@@ -8,14 +8,50 @@
 //! The list is intrusive, meaning that the linked list pointers
 //! are stored within the data structure itself, rather than in
 //! separate nodes that contain the data as payload.
-use pin_project::pin_project;
-use std::{
+//!
+//! The crate is `no_std` compatible when the default `std`
+//! feature is disabled, but it still requires an allocator.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+extern crate alloc;
+
+mod cursor;
+mod guard;
+mod iter;
+mod link;
+mod list;
+mod node_ref;
+mod raw;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod visitor;
+
+pub use cursor::Cursor;
+pub use iter::{CycleIter, ExactIter, Iter, IterMut};
+pub use link::{Adapter, Link};
+pub use list::{Drain, ExtractIf, List};
+pub use node_ref::{NodeId, NodeRef};
+pub use raw::{RawIter, RawNode};
+pub use visitor::{Collector, Counter, RingVisitor, RingVisitorMut};
+
+#[cfg(feature = "allocator_api")]
+use alloc::alloc::{handle_alloc_error, Allocator};
+use alloc::{alloc::Layout, boxed::Box, vec::Vec};
+#[cfg(feature = "debug-guard")]
+use core::cell::Cell;
+use core::{
+    array,
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
     marker::PhantomData,
-    mem::{offset_of, MaybeUninit},
-    ops::{Deref, DerefMut},
-    pin::Pin,
+    mem::{self, offset_of, ManuallyDrop, MaybeUninit},
+    num::NonZeroUsize,
+    ops::{ControlFlow, Deref, DerefMut},
     ptr::{self, NonNull},
 };
+use guard::Snapshot;
 
 /// Represents a node in a doubly-linked list.
 /// Contains user data of type `T` and links to the previous
@@ -47,20 +83,144 @@ use std::{
 /// /// should not compile
 /// impl_send(LinkNode::new(AtomicUsize::new(0)));
 /// ```
-pub struct LinkNode<T>(Pin<Box<Inner<T>>>);
+///
+/// `LinkNode<T>` is invariant in `T`. A node could otherwise be
+/// coerced to a shorter lifetime and linked with shorter-lived data,
+/// which the other nodes of its list would still hand out
+/// with the longer lifetime.
+/// ```compile_fail,E0597
+/// use cdlist::LinkNode;
+///
+/// let mut node0 = LinkNode::new("static");
+/// let mut node1: LinkNode<&'static str> = LinkNode::new("static");
+/// node0.add(&mut node1);
+/// let leaked: &'static str;
+/// {
+///     let short = String::from("short");
+///     /// should not compile
+///     let mut node1: LinkNode<&str> = node1;
+///     let mut node2 = LinkNode::new(short.as_str());
+///     node1.add(&mut node2);
+///     leaked = node0.nth(2, |&data| data).unwrap();
+/// }
+/// println!("{leaked}");
+/// ```
+///
+/// ```compile_fail
+/// use cdlist::LinkNode;
+///
+/// /// should not compile
+/// fn shorten<'a>(node: LinkNode<&'static str>) -> LinkNode<&'a str> {
+///     node
+/// }
+/// ```
+///
+/// Nodes of the same lifetime link freely.
+/// ```
+/// use cdlist::LinkNode;
+///
+/// fn link<'a>(node0: &mut LinkNode<&'a str>, node1: &mut LinkNode<&'a str>) {
+///     node0.add(node1);
+/// }
+///
+/// let short = String::from("short");
+/// let mut node0 = LinkNode::new("static");
+/// let mut node1 = LinkNode::new(short.as_str());
+/// link(&mut node0, &mut node1);
+/// assert_eq!(node0.to_vec(), ["static", "short"]);
+/// ```
+///
+/// Ignoring the result of a query is likely a mistake.
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use cdlist::LinkNode;
+///
+/// let node = LinkNode::new(0);
+/// /// should not compile
+/// node.len();
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// use cdlist::LinkNode;
+///
+/// /// should not compile
+/// LinkNode::try_new(0);
+/// ```
+pub struct LinkNode<T> {
+    inner: NonNull<Inner<T>>,
+    /// Owns the `Inner<T>`, and makes the type invariant in `T`.
+    marker: PhantomData<(Inner<T>, *mut T)>,
+}
 
 /// A private struct used by `LinkNode` to hold
 /// the user data and the links to the next and previous
 /// nodes in the list. This struct is not exposed outside
 /// the module.
 ///
-/// Pinned on heap for linking.
+/// Allocated on heap and never moved, for linking.
 ///
 /// T can be !Unpin.
-#[pin_project]
 struct Inner<T> {
     data: T,
     list: ListHead<T>,
+    /// Frees the allocation of the node, which may come
+    /// from a custom allocator, see `LinkNode::new_in`.
+    #[cfg(feature = "allocator_api")]
+    free: unsafe fn(NonNull<Inner<T>>),
+}
+
+impl<T> Inner<T> {
+    #[inline]
+    fn new(data: T) -> Self {
+        Self {
+            data,
+            list: ListHead::new(),
+            #[cfg(feature = "allocator_api")]
+            free: Self::free_box,
+        }
+    }
+
+    /// Moves the data out of the node at `this` and frees the node.
+    #[inline]
+    unsafe fn release(this: NonNull<Self>) -> T {
+        #[cfg(feature = "allocator_api")]
+        {
+            let free = (*this.as_ptr()).free;
+            let data = ptr::addr_of!((*this.as_ptr()).data).read();
+            free(this);
+            data
+        }
+        #[cfg(not(feature = "allocator_api"))]
+        Box::from_raw(this.as_ptr()).data
+    }
+
+    /// Frees a node allocated by the global allocator,
+    /// without dropping its data.
+    #[cfg(feature = "allocator_api")]
+    unsafe fn free_box(this: NonNull<Self>) {
+        drop(Box::from_raw(this.as_ptr().cast::<MaybeUninit<Self>>()));
+    }
+}
+
+/// A node allocated by a custom allocator,
+/// which is kept right after the node to free it.
+#[cfg(feature = "allocator_api")]
+#[repr(C)]
+struct InnerIn<T, A> {
+    inner: Inner<T>,
+    alloc: A,
+}
+
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> InnerIn<T, A> {
+    /// Frees a node allocated by `LinkNode::new_in`,
+    /// without dropping its data.
+    unsafe fn free(inner: NonNull<Inner<T>>) {
+        let this = inner.cast::<Self>();
+        let alloc = ptr::addr_of!((*this.as_ptr()).alloc).read();
+        alloc.deallocate(this.cast(), Layout::new::<Self>());
+    }
 }
 
 /// A private struct that represents the head of the linked list.
@@ -68,6 +228,9 @@ struct Inner<T> {
 struct ListHead<T> {
     prev: MaybeUninit<NonNull<ListHead<T>>>,
     next: MaybeUninit<NonNull<ListHead<T>>>,
+    /// Bumped whenever the links change, see `guard`.
+    #[cfg(feature = "debug-guard")]
+    generation: Cell<usize>,
     dtype: PhantomData<T>,
 }
 
@@ -75,31 +238,134 @@ impl<T> LinkNode<T> {
     /// Creates a new `LinkNode` with the provided user data.
     /// Initializes the node as a standalone element,
     /// effectively creating a new list.
+    #[must_use]
     #[inline]
     pub fn new(data: T) -> Self {
-        let mut node = Self(Box::pin(Inner {
-            data,
-            list: ListHead {
-                prev: MaybeUninit::uninit(),
-                next: MaybeUninit::uninit(),
-                dtype: PhantomData,
-            },
-        }));
+        Self::from_box(Box::new(Inner::new(data)))
+    }
+
+    /// Same as [`LinkNode::new`], but returns `Err(data)`
+    /// instead of aborting if the allocation fails.
+    ///
+    /// # Example
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let node = LinkNode::try_new(0).unwrap();
+    /// assert!(node.is_standalone());
+    /// ```
+    #[must_use = "dropping the result drops the data on allocation failure"]
+    pub fn try_new(data: T) -> Result<Self, T> {
+        let layout = Layout::new::<Inner<T>>();
+        // `Inner` always holds two pointers, so the layout is never zero-sized.
+        let ptr = unsafe { alloc::alloc::alloc(layout) }.cast::<Inner<T>>();
+        if ptr.is_null() {
+            return Err(data);
+        }
+        unsafe {
+            ptr.write(Inner::new(data));
+            Ok(Self::from_box(Box::from_raw(ptr)))
+        }
+    }
+
+    /// Creates a new `LinkNode` like [`LinkNode::new`],
+    /// allocating the node with `alloc`.
+    ///
+    /// The allocator is stored in the node and used to free it,
+    /// so nodes from different allocators can be linked together,
+    /// and every method works the same on them.
+    ///
+    /// # Example
+    /// ```
+    /// #![feature(allocator_api)]
+    /// use cdlist::LinkNode;
+    /// use std::alloc::Global;
+    ///
+    /// let mut node0 = LinkNode::new_in(0, Global);
+    /// let mut node1 = LinkNode::new(1);
+    /// node0.add(&mut node1);
+    /// assert_eq!(node1.to_vec(), [1, 0]);
+    /// ```
+    ///
+    /// The type of the node does not record the allocator,
+    /// so the allocator must not borrow anything.
+    /// ```compile_fail,E0597
+    /// #![feature(allocator_api)]
+    /// use cdlist::LinkNode;
+    /// use std::alloc::{AllocError, Allocator, Global, Layout};
+    /// use std::ptr::NonNull;
+    ///
+    /// struct ByRef<'a>(&'a Global);
+    ///
+    /// unsafe impl Allocator for ByRef<'_> {
+    ///     fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+    ///         self.0.allocate(layout)
+    ///     }
+    ///
+    ///     unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+    ///         self.0.deallocate(ptr, layout)
+    ///     }
+    /// }
+    ///
+    /// let node = {
+    ///     let global = Global;
+    ///     LinkNode::new_in(0, ByRef(&global))
+    /// };
+    /// ```
+    #[cfg(feature = "allocator_api")]
+    #[must_use]
+    pub fn new_in<A: Allocator + 'static>(data: T, alloc: A) -> Self {
+        let layout = Layout::new::<InnerIn<T, A>>();
+        let Ok(ptr) = alloc.allocate(layout) else {
+            handle_alloc_error(layout)
+        };
+        let ptr = ptr.cast::<InnerIn<T, A>>();
+        let inner = Inner {
+            free: InnerIn::<T, A>::free,
+            ..Inner::new(data)
+        };
         unsafe {
-            node.list_mut().init_head();
+            ptr.as_ptr().write(InnerIn { inner, alloc });
+            Self::from_inner(ptr.cast())
         }
+    }
+
+    /// Leaks `inner` and initializes it as a standalone node.
+    #[inline]
+    fn from_box(inner: Box<Inner<T>>) -> Self {
+        unsafe { Self::from_inner(NonNull::from(Box::leak(inner))) }
+    }
+
+    /// Takes ownership of the node at `inner`
+    /// and initializes it as a standalone node.
+    #[inline]
+    unsafe fn from_inner(inner: NonNull<Inner<T>>) -> Self {
+        let node = Self {
+            inner,
+            marker: PhantomData,
+        };
+        ListHead::init_head(node.head());
         node
     }
 
     /// Removes `other` from its current position in its list
     /// and inserts it after `self` in the current list.
+    ///
+    /// `other` is never `self`, as the two borrows cannot alias,
+    /// so this needs no runtime check.
+    /// ```compile_fail,E0499
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// node0.add(&mut node0);
+    /// ```
     #[inline]
     pub fn add(&mut self, other: &mut LinkNode<T>) {
-        let self_list = self.list_mut();
-        let other_list = other.list_mut();
+        let self_list = self.head();
+        let other_list = other.head();
         unsafe {
-            other_list.delist();
-            self_list.add(other_list);
+            ListHead::delist(other_list);
+            ListHead::add(self_list, other_list);
         }
     }
 
@@ -110,232 +376,2804 @@ impl<T> LinkNode<T> {
         other.add(self)
     }
 
+    /// Removes `other` from its current position in its list
+    /// and inserts it before `self` in the current list.
+    #[inline]
+    pub fn add_before(&mut self, other: &mut LinkNode<T>) {
+        let self_list = self.head();
+        let other_list = other.head();
+        unsafe {
+            ListHead::delist(other_list);
+            ListHead::add_before(self_list, other_list);
+        }
+    }
+
+    /// Adds `self` to the list of `other`, right before `other`.
+    /// It's a convenience method that effectively calls `other.add_before(self)`.
+    #[inline]
+    pub fn add_before_to(&mut self, other: &mut LinkNode<T>) {
+        other.add_before(self)
+    }
+
+    /// Moves the whole list containing `other` into the current list,
+    /// right after `self`, keeping the order of its elements
+    /// starting from `other`. This is O(1).
+    ///
+    /// `self` and `other` must be in different lists, which is checked
+    /// in debug builds only. The check walks the list and is O(n).
+    #[inline]
+    pub fn append(&mut self, other: &mut LinkNode<T>) {
+        let self_list = self.head();
+        let other_list = other.head();
+        debug_assert!(
+            !unsafe { ListHead::reaches(self_list, other_list) },
+            "`self` and `other` must be in different lists"
+        );
+        unsafe { ListHead::splice(self_list, other_list) };
+    }
+
+    /// Same as [`LinkNode::append`], but `self` and `other`
+    /// may also be in the same list.
+    ///
+    /// Then the list is rotated so that `other` comes right after `self`:
+    /// the nodes from `other` up to but excluding `self` are moved
+    /// right after `self`, which is the same as moving `self`
+    /// right before `other`.
+    ///
+    /// Telling the two cases apart walks the list of `self`,
+    /// so this is O(n), unlike `append`.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// node0.splice_after(&mut node2);
+    /// assert_eq!(node0.to_vec(), [0, 2, 1]);
+    /// ```
+    pub fn splice_after(&mut self, other: &mut LinkNode<T>) {
+        let self_list = self.head();
+        let other_list = other.head();
+        unsafe {
+            if !ListHead::reaches(self_list, other_list) {
+                ListHead::splice(self_list, other_list);
+            } else if self_list != other_list {
+                ListHead::delist(self_list);
+                ListHead::add_before(other_list, self_list);
+            }
+        }
+    }
+
+    /// Moves the whole list containing `other` into the current list,
+    /// right before `self`, keeping the order of its elements
+    /// starting from `other`: `other` becomes the first of the inserted
+    /// nodes, and the node before `other` the last one, right before `self`.
+    /// This is O(1).
+    ///
+    /// Like [`LinkNode::append`], `self` and `other` must be in different
+    /// lists, which is checked in debug builds only.
+    /// The check walks the list and is O(n).
+    #[inline]
+    pub fn splice_before(&mut self, other: &mut LinkNode<T>) {
+        let self_list = self.head();
+        let other_list = other.head();
+        debug_assert!(
+            !unsafe { ListHead::reaches(self_list, other_list) },
+            "`self` and `other` must be in different lists"
+        );
+        unsafe { ListHead::splice(ListHead::prev_ptr(self_list), other_list) };
+    }
+
+    /// Splits the list `head` belongs to into two lists:
+    /// the nodes from `head` up to but excluding `self` stay in one list,
+    /// and the nodes from `self` up to but excluding `head`
+    /// form another.
+    ///
+    /// `self` and `head` must be in the same list, which is checked
+    /// in debug builds only. The check walks the list and is O(n).
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// let mut node3 = LinkNode::new(3);
+    /// node0.add(&mut node3);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// node2.split_off(&mut node0);
+    /// assert_eq!(node0.to_vec(), [0, 1]);
+    /// assert_eq!(node2.to_vec(), [2, 3]);
+    /// ```
+    #[inline]
+    pub fn split_off(&mut self, head: &mut LinkNode<T>) {
+        let self_list = self.head();
+        let head_list = head.head();
+        debug_assert!(
+            unsafe { ListHead::reaches(head_list, self_list) },
+            "`self` and `head` must be in the same list"
+        );
+        unsafe { ListHead::split(self_list, head_list) };
+    }
+
+    /// Splits the list `self` belongs to into two lists:
+    /// the nodes after `self` up to and including `other` form
+    /// a new list, and the others, starting from `self`, stay together.
+    ///
+    /// `self` and `other` must be in the same list, which is checked
+    /// in debug builds only. The check walks the list and is O(n).
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// let mut node3 = LinkNode::new(3);
+    /// node0.add(&mut node3);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// node0.split_after(&mut node2);
+    /// assert_eq!(node0.to_vec(), [0, 3]);
+    /// assert_eq!(node1.to_vec(), [1, 2]);
+    /// ```
+    #[inline]
+    pub fn split_after(&mut self, other: &mut LinkNode<T>) {
+        let self_list = self.head();
+        let other_list = other.head();
+        debug_assert!(
+            unsafe { ListHead::reaches(self_list, other_list) },
+            "`self` and `other` must be in the same list"
+        );
+        unsafe {
+            ListHead::split(
+                ListHead::next_ptr(self_list),
+                ListHead::next_ptr(other_list),
+            )
+        };
+    }
+
+    /// Reverses the order of all elements in the list in place,
+    /// by swapping the previous and next links of every node.
+    /// This is O(n) and does not allocate.
+    #[inline]
+    pub fn reverse(&mut self) {
+        unsafe { ListHead::reverse(self.head()) };
+    }
+
+    /// Exchanges the positions of `self` and `other`, leaving
+    /// their data in place: `self` takes the place of `other`
+    /// in its list, and `other` the place of `self`.
+    ///
+    /// The nodes can be in the same list, adjacent or not,
+    /// or in different lists. This is O(1).
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// node0.swap(&mut node2);
+    /// assert_eq!(node1.to_vec(), [1, 0, 2]);
+    /// ```
+    #[doc(alias = "swap_positions")]
+    #[inline]
+    pub fn swap(&mut self, other: &mut LinkNode<T>) {
+        let self_list = self.head();
+        let other_list = other.head();
+        if self_list != other_list {
+            unsafe { ListHead::swap(self_list, other_list) };
+        }
+    }
+
+    /// Sorts the list `self` belongs to with `compare`,
+    /// so that reading it forward from its smallest element
+    /// gives the elements in ascending order.
+    ///
+    /// The sort is stable: equal elements keep their order,
+    /// read forward from `self`. It is a merge sort relinking
+    /// the nodes in O(n log n), which neither moves the data
+    /// nor allocates. `self` keeps its data, and ends up
+    /// wherever its data belongs in the sorted list.
+    ///
+    /// If `compare` panics, the list still holds all its nodes,
+    /// in an unspecified order.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(3);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// node0.sort_by(|a, b| a.cmp(b));
+    /// assert_eq!(node1.to_vec(), [1, 2, 3]);
+    /// assert_eq!(node0.to_vec(), [3, 1, 2]);
+    /// ```
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        unsafe { ListHead::sort_by(self.head(), compare) };
+    }
+
+    /// Removes `self` from its current list and returns its data,
+    /// freeing the node.
+    #[must_use]
+    #[inline]
+    pub fn into_inner(self) -> T {
+        let this = ManuallyDrop::new(self);
+        unsafe { ListHead::delist(this.head()) };
+        // the node is no longer referenced by any other node,
+        // so it is safe to move the data out of it.
+        unsafe { Inner::release(this.inner) }
+    }
+
+    /// Replaces the data of `self` with `data` and returns the old data,
+    /// leaving the node at its position in the list.
+    #[must_use = "if you don't need the old value, you can just assign the new value directly"]
+    #[inline]
+    pub fn replace(&mut self, data: T) -> T {
+        mem::replace(&mut **self, data)
+    }
+
     /// Removes `self` from its current list,
     /// turning it into a standalone element.
     #[inline]
     pub fn take(&mut self) {
-        let list = self.list_mut();
+        let list = self.head();
         unsafe {
-            list.delist();
-            list.init_head();
+            ListHead::delist(list);
+            ListHead::init_head(list);
         }
     }
 
-    /// Iterates over each element in the list starting from `self`
-    /// and applies function `f` to an immutable reference
-    /// to each element's data.
-    pub fn for_each<F>(&self, f: F)
-    where
-        F: FnMut(&T),
-    {
-        self.list().for_each(f)
+    /// Removes `other` from its current list and puts it in
+    /// the place of `self`, which becomes a standalone element.
+    ///
+    /// If `self` is standalone, `other` ends up standalone too.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// node0.add(&mut node1);
+    ///
+    /// let mut new = LinkNode::new(9);
+    /// node1.replace_with(&mut new);
+    /// assert!(node1.is_standalone());
+    /// assert_eq!(node0.to_vec(), [0, 9]);
+    /// ```
+    #[inline]
+    pub fn replace_with(&mut self, other: &mut LinkNode<T>) {
+        self.add_before(other);
+        self.take();
     }
 
-    /// Iterates over each element in the list starting from `self`
-    /// and applies function `f` to a mutable reference
-    /// to each element's data.
-    pub fn for_each_mut<F>(&mut self, f: F)
-    where
-        F: FnMut(&mut T),
-    {
-        self.list_mut().for_each_mut(f)
+    /// Returns the number of nodes in the list `self` belongs to,
+    /// including `self`. A standalone node has length 1.
+    ///
+    /// This is O(n). The list has no head that could cache its length:
+    /// every node is an equally valid entry point, and `add`, `take`
+    /// or `append` would have to update a count stored in every node.
+    /// [`List`] has a sentinel head, and caches its length for an O(1)
+    /// [`List::len`].
+    ///
+    /// A list always contains at least `self`, so there is no `is_empty`.
+    #[must_use]
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.count()
     }
 
-    /// Iterates over each element in the list starting from `self`
-    /// in reverse order and applies function `f` to an immutable reference
-    /// to each element's data.
-    pub fn for_each_rev<F>(&self, f: F)
+    /// Counts the nodes in the list `self` belongs to,
+    /// including `self`, by walking the whole list.
+    #[must_use]
+    #[inline]
+    pub fn count(&self) -> usize {
+        let mut count = 0;
+        unsafe { ListHead::for_each(self.head(), |_| count += 1) };
+        count
+    }
+
+    /// Returns the number of elements in the list,
+    /// including `self`, that satisfy `predicate`.
+    pub fn count_if<P>(&self, mut predicate: P) -> usize
     where
-        F: FnMut(&T),
+        P: FnMut(&T) -> bool,
     {
-        self.list().for_each_rev(f)
+        Iter::new(self).filter(|data| predicate(data)).count()
     }
 
-    /// Iterates over each element in the list starting from `self`
-    /// in reverse order and applies function `f` to a mutable reference
-    /// to each element's data.
-    pub fn for_each_mut_rev<F>(&mut self, f: F)
+    /// Returns the number of elements in the list,
+    /// excluding `self`, that satisfy `predicate`.
+    pub fn count_others_if<P>(&self, mut predicate: P) -> usize
     where
-        F: FnMut(&mut T),
+        P: FnMut(&T) -> bool,
     {
-        self.list_mut().for_each_rev_mut(f)
+        Iter::new(self)
+            .skip(1)
+            .filter(|data| predicate(data))
+            .count()
     }
 
-    #[inline(always)]
-    fn list(&self) -> &ListHead<T> {
-        &self.0.list
+    /// Returns `true` if `self` is the only node in its list.
+    /// This is O(1), unlike comparing `len()` with 1.
+    #[must_use]
+    #[inline]
+    pub fn is_standalone(&self) -> bool {
+        unsafe { ListHead::is_standalone(self.head()) }
     }
 
-    #[inline(always)]
-    fn list_mut(&mut self) -> &mut ListHead<T> {
-        self.0.as_mut().project().list
+    /// Returns `true` if the list `self` belongs to has a single node.
+    /// Same as [`LinkNode::is_standalone`], by checking that the next
+    /// node is `self` in O(1).
+    #[must_use]
+    #[inline]
+    pub fn is_singleton(&self) -> bool {
+        self.is_standalone()
     }
-}
 
-impl<T> DerefMut for LinkNode<T> {
+    /// Returns an iterator over immutable references to the data
+    /// of each element in the list, starting from `self`.
+    ///
+    /// # Safety
+    ///
+    /// The iterator only borrows `self`, but yields references into
+    /// the other nodes of the list. While the iterator or any of those
+    /// references is alive, no other node of the list may be dropped,
+    /// relinked, or mutated through its own handle.
+    ///
+    /// The closure methods such as [`LinkNode::for_each`] have no
+    /// such requirement.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// // no node is dropped or modified while the iterators are alive
+    /// let odd = unsafe { node0.iter() }.filter(|&&i| i % 2 == 1).count();
+    /// assert_eq!(odd, 1);
+    /// let vec = unsafe { node1.iter() }.copied().collect::<Vec<_>>();
+    /// assert_eq!(vec, vec![1, 2, 0]);
+    /// ```
     #[inline]
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        self.0.as_mut().project().data
+    pub unsafe fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self)
     }
-}
 
-impl<T> Deref for LinkNode<T> {
-    type Target = T;
+    /// Returns an iterator like [`LinkNode::iter`] that also
+    /// implements `ExactSizeIterator`.
+    ///
+    /// The list is walked once up front to count its nodes,
+    /// so creating this iterator is O(n).
+    ///
+    /// # Safety
+    ///
+    /// Same as [`LinkNode::iter`].
     #[inline]
-    fn deref(&self) -> &Self::Target {
-        &self.0.data
+    pub unsafe fn iter_exact(&self) -> ExactIter<'_, T> {
+        ExactIter::new(Iter::new(self), self.len())
     }
-}
 
-impl<T> Drop for LinkNode<T> {
-    fn drop(&mut self) {
-        unsafe { self.list_mut().delist() };
+    /// Returns an iterator that never ends: after the last element
+    /// it wraps around to `self` and continues.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`LinkNode::iter`].
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// node0.add(&mut node1);
+    ///
+    /// let vec = unsafe { node0.cycle_iter() }.take(5).copied().collect::<Vec<_>>();
+    /// assert_eq!(vec, vec![0, 1, 0, 1, 0]);
+    /// ```
+    #[inline]
+    pub unsafe fn cycle_iter(&self) -> CycleIter<'_, T> {
+        CycleIter::new(self)
     }
-}
 
-impl<T> ListHead<T> {
-    #[inline(always)]
-    unsafe fn ptr(&mut self) -> NonNull<ListHead<T>> {
-        NonNull::from(self)
+    /// Returns an iterator over mutable references to the data
+    /// of each element in the list, starting from `self`.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`LinkNode::iter`], and no other node of the list
+    /// may be read through its own handle either: that would alias
+    /// the mutable references.
+    ///
+    /// [`LinkNode::for_each_mut`] has no such requirement.
+    #[inline]
+    pub unsafe fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut::new(self)
     }
 
-    /// Initializes the list head, setting the previous and
-    /// next pointers to point to itself, effectively creating an empty list.
-    #[inline(always)]
-    unsafe fn init_head(&mut self) {
-        let self_ptr = self.ptr();
-        self.prev.write(self_ptr);
-        self.next.write(self_ptr);
+    /// Returns a cursor starting at `self`, which can remove
+    /// and insert nodes while walking the list.
+    ///
+    /// # Safety
+    ///
+    /// The cursor only borrows `self`, but points at the other nodes
+    /// of the list. While it is alive, no other node of the list,
+    /// including the ones inserted by the cursor, may be dropped,
+    /// relinked, or accessed through its own handle other than
+    /// by passing it to [`Cursor::insert_after`].
+    #[inline]
+    pub unsafe fn cursor_mut(&mut self) -> Cursor<'_, T> {
+        Cursor::new(self)
     }
 
-    /// Removes the current node from its list by updating the
-    /// previous and next nodes to point to each other.
-    /// This method leaves the current node in an inconsistent state
-    /// and should be followed by reinsertion into a list using `add` or
-    /// resetting the pointers using `init_head`.
-    #[inline(always)]
-    unsafe fn delist(&mut self) {
-        let mut prev = self.prev.assume_init();
-        let mut next = self.next.assume_init();
-        prev.as_mut().next.write(next);
-        next.as_mut().prev.write(prev);
+    /// Deep copies the list starting from `self` into a brand new list,
+    /// independent of the current one.
+    ///
+    /// Returns the handles of the new nodes in list order,
+    /// so the first one corresponds to `self`.
+    /// This is O(n) and allocates n boxes.
+    ///
+    /// `LinkNode` does not implement `Clone` returning only the node
+    /// for `self`: that single handle could not own the other new nodes.
+    #[must_use]
+    pub fn clone_list(&self) -> Vec<LinkNode<T>>
+    where
+        T: Clone,
+    {
+        let mut nodes = Iter::new(self)
+            .map(|data| Self::new(data.clone()))
+            .collect::<Vec<_>>();
+        if let Some((first, rest)) = nodes.split_first_mut() {
+            for node in rest.iter_mut() {
+                first.add_before(node);
+            }
+        }
+        nodes
     }
 
-    /// Inserts `other` between `self` and the node currently following `self`.
-    /// Assumes `other` is not part of any list.
-    #[inline(always)]
-    unsafe fn add(&mut self, other: &mut ListHead<T>) {
-        let self_ptr = self.ptr();
-        let other_ptr = other.ptr();
-        let next_ptr = self.next.assume_init();
-        let next = self.next.assume_init_mut().as_mut();
+    /// Returns the identifier of `self`, which equals the
+    /// [`NodeRef::id`] of `self` visited in a traversal.
+    #[must_use]
+    #[inline]
+    pub fn id(&self) -> NodeId {
+        NodeId::new(self.head())
+    }
 
-        other.prev.write(self_ptr);
-        other.next.write(next_ptr);
-        next.prev.write(other_ptr);
-        self.next.write(other_ptr);
+    /// Iterates over each node in the list starting from `self`
+    /// and applies function `f` to a read-only handle to each node.
+    pub fn for_each_node<F>(&self, mut f: F)
+    where
+        F: FnMut(NodeRef<'_, T>),
+    {
+        let start = self.head();
+        let mut index = 0;
+        unsafe {
+            ListHead::walk(start, ListHead::next_ptr, |list| {
+                f(NodeRef::new(list, start, index));
+                index += 1;
+            })
+        }
     }
 
-    #[inline(always)]
-    fn for_each<F>(&self, mut f: F)
+    /// Applies function `f` to a read-only handle to the node `n` steps
+    /// forward from `self`, wrapping around the list as many times
+    /// as needed, and returns its result.
+    ///
+    /// The list is not modified: this only moves a handle,
+    /// e.g. to advance the current head of a round-robin,
+    /// which can be kept as its [`NodeRef::id`].
+    /// Its [`NodeRef::index`] is counted forward from `self`.
+    /// This is O(len) to reduce `n` modulo the length.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// assert_eq!(node0.rotate_forward(4, |node| *node.data()), 1);
+    /// assert_eq!(node0.rotate_backward(4, |node| *node.data()), 2);
+    /// assert_eq!(node0.rotate_forward(1, |node| node.id()), node1.id());
+    /// ```
+    pub fn rotate_forward<F, R>(&self, n: usize, f: F) -> R
     where
-        F: FnMut(&T),
+        F: FnOnce(NodeRef<'_, T>) -> R,
     {
-        let self_ptr = ptr::from_ref(self);
-        let mut this = self;
-        loop {
-            f(this.get());
-            let next = unsafe { this.next.assume_init_ref() };
-            if ptr::addr_eq(next.as_ptr(), self_ptr) {
-                break;
-            }
-            this = unsafe { next.as_ref() };
+        let start = self.head();
+        let index = n % self.len();
+        let mut this = start;
+        for _ in 0..index {
+            this = unsafe { ListHead::next_ptr(this) };
         }
+        f(unsafe { NodeRef::new(this, start, index) })
     }
 
-    #[inline(always)]
-    fn for_each_mut<F>(&mut self, mut f: F)
+    /// Same as [`LinkNode::rotate_forward`], but steps backward.
+    /// The index of the handle passed to `f` is still counted forward
+    /// from `self`.
+    pub fn rotate_backward<F, R>(&self, n: usize, f: F) -> R
     where
-        F: FnMut(&mut T),
+        F: FnOnce(NodeRef<'_, T>) -> R,
     {
-        let self_ptr = ptr::from_ref(self);
-        let mut this = self;
-        loop {
-            f(this.get_mut());
-            let next = unsafe { this.next.assume_init_mut() };
-            if ptr::addr_eq(next.as_ptr(), self_ptr) {
-                break;
-            }
-            this = unsafe { next.as_mut() };
+        let start = self.head();
+        let len = self.len();
+        let steps = n % len;
+        let mut this = start;
+        for _ in 0..steps {
+            this = unsafe { ListHead::prev_ptr(this) };
         }
+        f(unsafe { NodeRef::new(this, start, (len - steps) % len) })
     }
 
-    #[inline(always)]
-    fn for_each_rev<F>(&self, mut f: F)
+    /// Returns a raw handle to `self`.
+    ///
+    /// Creating the handle is safe, but using it is not,
+    /// see [`LinkNode::data_from_raw`] and [`LinkNode::next_raw`].
+    #[must_use]
+    #[inline]
+    pub fn as_raw(&self) -> RawNode<T> {
+        RawNode::new(self.head())
+    }
+
+    /// Returns an iterator over raw handles to each node
+    /// in the list, starting from `self`.
+    ///
+    /// # Safety
+    ///
+    /// The iterator is not tied to the lifetime of `self`.
+    /// While it is being used, every node in the list must stay alive,
+    /// and the list must not be modified.
+    #[inline]
+    pub unsafe fn raw_iter(&self) -> RawIter<T> {
+        RawIter::new(self.head())
+    }
+
+    /// Returns an immutable reference to the data of the node
+    /// `handle` refers to.
+    ///
+    /// # Safety
+    ///
+    /// The node must be alive for the whole lifetime `'a`,
+    /// and its data must not be mutably borrowed during that time.
+    #[inline]
+    pub unsafe fn data_from_raw<'a>(handle: RawNode<T>) -> &'a T {
+        ListHead::data_ptr(handle.as_ptr()).as_ref()
+    }
+
+    /// Returns a raw handle to the node after the node
+    /// `handle` refers to.
+    ///
+    /// # Safety
+    ///
+    /// The node `handle` refers to must be alive.
+    #[inline]
+    pub unsafe fn next_raw(handle: RawNode<T>) -> RawNode<T> {
+        RawNode::new(ListHead::next_ptr(handle.as_ptr()))
+    }
+
+    /// Returns a raw handle to the node before the node
+    /// `handle` refers to.
+    ///
+    /// # Safety
+    ///
+    /// The node `handle` refers to must be alive.
+    #[inline]
+    pub unsafe fn prev_raw(handle: RawNode<T>) -> RawNode<T> {
+        RawNode::new(ListHead::prev_ptr(handle.as_ptr()))
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to an immutable reference
+    /// to each element's data.
+    pub fn for_each<F>(&self, f: F)
     where
         F: FnMut(&T),
     {
-        let self_ptr = ptr::from_ref(self);
-        let mut this = self;
-        loop {
-            f(this.get());
-            let prev = unsafe { this.prev.assume_init_ref() };
-            if ptr::addr_eq(prev.as_ptr(), self_ptr) {
-                break;
-            }
-            this = unsafe { prev.as_ref() };
-        }
+        unsafe { ListHead::for_each(self.head(), f) }
     }
 
-    #[inline(always)]
-    fn for_each_rev_mut<F>(&mut self, mut f: F)
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to a mutable reference
+    /// to each element's data.
+    pub fn for_each_mut<F>(&mut self, f: F)
     where
         F: FnMut(&mut T),
     {
-        let self_ptr = ptr::from_ref(self);
-        let mut this = self;
-        loop {
-            f(this.get_mut());
-            let prev = unsafe { this.prev.assume_init_mut() };
-            if ptr::addr_eq(prev.as_ptr(), self_ptr) {
-                break;
-            }
-            this = unsafe { prev.as_mut() };
-        }
+        unsafe { ListHead::for_each_mut(self.head(), f) }
     }
 
-    /// Returns an immutable reference to the data contained in the
-    /// `Inner<T>` struct associated with `self`.
-    #[inline(always)]
-    fn get(&self) -> &T {
-        unsafe { &self.inner().data }
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to an immutable reference
+    /// to each element's data.
+    pub fn for_each_rev<F>(&self, f: F)
+    where
+        F: FnMut(&T),
+    {
+        unsafe { ListHead::for_each_rev(self.head(), f) }
     }
 
-    /// Returns a mutable reference to the data contained in the
-    /// `Inner<T>` struct associated with `self`.
-    #[inline(always)]
-    fn get_mut(&mut self) -> &mut T {
-        unsafe { &mut self.inner_mut().data }
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to a mutable reference
+    /// to each element's data.
+    pub fn for_each_mut_rev<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        unsafe { ListHead::for_each_rev_mut(self.head(), f) }
     }
 
-    #[inline(always)]
-    unsafe fn inner(&self) -> &Inner<T> {
-        &*(ptr::from_ref(self)
-            .byte_offset(Self::offset())
-            .cast::<Inner<T>>())
+    /// Iterates over each element in the list except `self`,
+    /// starting from the node after `self`, and applies function `f`
+    /// to an immutable reference to each element's data.
+    ///
+    /// Does nothing if `self` is standalone.
+    pub fn for_each_others<F>(&self, f: F)
+    where
+        F: FnMut(&T),
+    {
+        Iter::new(self).skip(1).for_each(f)
     }
 
-    #[inline(always)]
-    unsafe fn inner_mut(&mut self) -> &mut Inner<T> {
-        &mut *(ptr::from_mut(self)
-            .byte_offset(Self::offset())
-            .cast::<Inner<T>>())
+    /// Iterates over each element in the list except `self`,
+    /// starting from the node after `self`, and applies function `f`
+    /// to a mutable reference to each element's data.
+    ///
+    /// Does nothing if `self` is standalone.
+    pub fn for_each_others_mut<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        IterMut::new(self).skip(1).for_each(f)
     }
 
-    /// The compiler will compile this into an inlined constant
-    /// even without inline const feature.
-    #[inline(always)]
-    const fn offset() -> isize {
-        -(offset_of!(Inner<T>, list) as isize)
+    /// Iterates over each element in the list except `self`
+    /// in reverse order, starting from the node before `self`,
+    /// and applies function `f` to an immutable reference
+    /// to each element's data.
+    ///
+    /// Does nothing if `self` is standalone.
+    pub fn for_each_others_rev<F>(&self, f: F)
+    where
+        F: FnMut(&T),
+    {
+        let mut iter = Iter::new(self);
+        iter.next();
+        iter.rev().for_each(f)
+    }
+
+    /// Iterates over each element in the list except `self`
+    /// in reverse order, starting from the node before `self`,
+    /// and applies function `f` to a mutable reference
+    /// to each element's data.
+    ///
+    /// Does nothing if `self` is standalone.
+    pub fn for_each_others_mut_rev<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        let mut iter = IterMut::new(self);
+        iter.next();
+        iter.rev().for_each(f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to the position of each element
+    /// relative to `self` and an immutable reference to its data.
+    pub fn for_each_indexed<F>(&self, mut f: F)
+    where
+        F: FnMut(usize, &T),
+    {
+        let mut index = 0;
+        unsafe {
+            ListHead::for_each(self.head(), |data| {
+                f(index, data);
+                index += 1;
+            })
+        }
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to the position of each element
+    /// relative to `self` and a mutable reference to its data.
+    pub fn for_each_indexed_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &mut T),
+    {
+        let mut index = 0;
+        unsafe {
+            ListHead::for_each_mut(self.head(), |data| {
+                f(index, data);
+                index += 1;
+            })
+        }
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to the position
+    /// of each element counting backwards from `self`
+    /// and an immutable reference to its data.
+    pub fn for_each_indexed_rev<F>(&self, mut f: F)
+    where
+        F: FnMut(usize, &T),
+    {
+        let mut index = 0;
+        unsafe {
+            ListHead::for_each_rev(self.head(), |data| {
+                f(index, data);
+                index += 1;
+            })
+        }
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to the position
+    /// of each element counting backwards from `self`
+    /// and a mutable reference to its data.
+    pub fn for_each_indexed_mut_rev<F>(&mut self, mut f: F)
+    where
+        F: FnMut(usize, &mut T),
+    {
+        let mut index = 0;
+        unsafe {
+            ListHead::for_each_rev_mut(self.head(), |data| {
+                f(index, data);
+                index += 1;
+            })
+        }
+    }
+
+    /// Applies function `f` to an immutable reference to the data
+    /// of the node after `self` and returns its result,
+    /// or `None` if `self` is standalone.
+    ///
+    /// The data is only lent to `f`, as `self` does not borrow
+    /// the next node.
+    #[inline]
+    pub fn peek_next<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let list = self.head();
+        unsafe {
+            if ListHead::is_standalone(list) {
+                return None;
+            }
+            Some(f(ListHead::data_ptr(ListHead::next_ptr(list)).as_ref()))
+        }
+    }
+
+    /// Same as [`LinkNode::peek_next`], for the node before `self`.
+    #[inline]
+    pub fn peek_prev<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        let list = self.head();
+        unsafe {
+            if ListHead::is_standalone(list) {
+                return None;
+            }
+            Some(f(ListHead::data_ptr(ListHead::prev_ptr(list)).as_ref()))
+        }
+    }
+
+    /// Applies function `f` to each pair of adjacent elements
+    /// `(current, next)` going forward from `self`,
+    /// including the wrap-around pair `(last, self)`.
+    ///
+    /// A list of n nodes yields n pairs, so a standalone node
+    /// calls `f(x, x)` exactly once with its own data.
+    ///
+    /// There is no mutable variant, since on a standalone node
+    /// both references would alias.
+    pub fn for_each_pair<F>(&self, mut f: F)
+    where
+        F: FnMut(&T, &T),
+    {
+        let mut iter = Iter::new(self);
+        let first = iter.next().expect("list is never empty");
+        let last = iter.fold(first, |prev, next| {
+            f(prev, next);
+            next
+        });
+        f(last, first);
+    }
+
+    /// Applies function `f` to each pair of adjacent elements
+    /// `(current, next)` going forward from `self`,
+    /// treating the list as a linear sequence starting at `self`.
+    ///
+    /// Unlike [`LinkNode::for_each_pair`], the wrap-around pair
+    /// `(last, self)` is omitted, so a list of n nodes yields n - 1 pairs.
+    pub fn for_each_pair_open<F>(&self, mut f: F)
+    where
+        F: FnMut(&T, &T),
+    {
+        let mut iter = Iter::new(self);
+        let first = iter.next().expect("list is never empty");
+        iter.fold(first, |prev, next| {
+            f(prev, next);
+            next
+        });
+    }
+
+    /// Applies function `f` to each window of `N` consecutive elements
+    /// going forward from `self`, wrapping around the list.
+    ///
+    /// Exactly one window starts at each node, so a list of n nodes
+    /// yields n windows. If the list is shorter than `N`,
+    /// the windows wrap around more than once and contain
+    /// some elements repeatedly. With `N = 1` this is `for_each`.
+    pub fn for_each_windows<const N: usize, F>(&self, mut f: F)
+    where
+        F: FnMut(&[&T; N]),
+    {
+        let mut ahead = CycleIter::new(self);
+        let mut window: [&T; N] = array::from_fn(|_| ahead.next().unwrap());
+        for _ in Iter::new(self) {
+            f(&window);
+            if N > 0 {
+                window.rotate_left(1);
+                window[N - 1] = ahead.next().unwrap();
+            }
+        }
+    }
+
+    /// Iterates over at most `k` elements in the list starting from `self`
+    /// and applies function `f` to an immutable reference
+    /// to each element's data. Never visits a node twice,
+    /// even if `k` is larger than the list.
+    pub fn for_each_take<F>(&self, k: usize, f: F)
+    where
+        F: FnMut(&T),
+    {
+        Iter::new(self).take(k).for_each(f)
+    }
+
+    /// Iterates over at most `k` elements in the list starting from `self`
+    /// and applies function `f` to a mutable reference
+    /// to each element's data. Never visits a node twice,
+    /// even if `k` is larger than the list.
+    pub fn for_each_take_mut<F>(&mut self, k: usize, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        IterMut::new(self).take(k).for_each(f)
+    }
+
+    /// Iterates over at most `k` elements in the list starting from `self`
+    /// in reverse order and applies function `f` to an immutable reference
+    /// to each element's data. Never visits a node twice,
+    /// even if `k` is larger than the list.
+    pub fn for_each_take_rev<F>(&self, k: usize, f: F)
+    where
+        F: FnMut(&T),
+    {
+        Iter::new_rev(self).take(k).for_each(f)
+    }
+
+    /// Iterates over at most `k` elements in the list starting from `self`
+    /// in reverse order and applies function `f` to a mutable reference
+    /// to each element's data. Never visits a node twice,
+    /// even if `k` is larger than the list.
+    pub fn for_each_take_mut_rev<F>(&mut self, k: usize, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        IterMut::new_rev(self).take(k).for_each(f)
+    }
+
+    /// Iterates over every `step`-th element in the list starting
+    /// from `self` and applies function `f` to an immutable reference
+    /// to each element's data. Stops before wrapping around to `self`,
+    /// so no node is visited twice.
+    pub fn for_each_step_by<F>(&self, step: NonZeroUsize, f: F)
+    where
+        F: FnMut(&T),
+    {
+        Iter::new(self).step_by(step.get()).for_each(f)
+    }
+
+    /// Iterates over every `step`-th element in the list starting
+    /// from `self` and applies function `f` to a mutable reference
+    /// to each element's data. Stops before wrapping around to `self`,
+    /// so no node is visited twice.
+    pub fn for_each_step_by_mut<F>(&mut self, step: NonZeroUsize, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        IterMut::new(self).step_by(step.get()).for_each(f)
+    }
+
+    /// Iterates over the list starting from `self` in chunks of `n`
+    /// consecutive elements and applies function `f` to a slice
+    /// of immutable references to the data of each chunk.
+    /// The last chunk may be shorter than `n`.
+    pub fn for_each_chunks<F>(&self, n: NonZeroUsize, mut f: F)
+    where
+        F: FnMut(&[&T]),
+    {
+        let mut chunk = Vec::with_capacity(n.get());
+        for data in Iter::new(self) {
+            chunk.push(data);
+            if chunk.len() == n.get() {
+                f(&chunk);
+                chunk.clear();
+            }
+        }
+        if !chunk.is_empty() {
+            f(&chunk);
+        }
+    }
+
+    /// Iterates over the list starting from `self` in chunks of `n`
+    /// consecutive elements and applies function `f` to a slice
+    /// of mutable references to the data of each chunk.
+    /// The last chunk may be shorter than `n`.
+    pub fn for_each_chunks_mut<F>(&mut self, n: NonZeroUsize, mut f: F)
+    where
+        F: FnMut(&mut [&mut T]),
+    {
+        let mut chunk = Vec::with_capacity(n.get());
+        for data in IterMut::new(self) {
+            chunk.push(data);
+            if chunk.len() == n.get() {
+                f(&mut chunk);
+                chunk.clear();
+            }
+        }
+        if !chunk.is_empty() {
+            f(&mut chunk);
+        }
+    }
+
+    /// Clones the data of each element in the list starting from `self`
+    /// into a new `Vec`.
+    #[must_use]
+    #[inline]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        Iter::new(self).cloned().collect()
+    }
+
+    /// Iterates over the list starting from `self` and the list
+    /// starting from `other` in lockstep, and applies function `f`
+    /// to immutable references to the data of each pair of elements.
+    /// Stops as soon as the shorter list has been walked through.
+    pub fn zip_for_each<U, F>(&self, other: &LinkNode<U>, mut f: F)
+    where
+        F: FnMut(&T, &U),
+    {
+        Iter::new(self)
+            .zip(Iter::new(other))
+            .for_each(|(a, b)| f(a, b))
+    }
+
+    /// Iterates over the list starting from `self` and the list
+    /// starting from `other` in lockstep, and applies function `f`
+    /// to a mutable reference to the data of each element of `self`'s
+    /// list and an immutable reference to the data of its counterpart.
+    /// Stops as soon as the shorter list has been walked through.
+    ///
+    /// If `self` and `other` are in the same list, the two walks keep
+    /// a constant distance from each other, so they never reach
+    /// the same node at the same step.
+    pub fn zip_for_each_mut<U, F>(&mut self, other: &LinkNode<U>, mut f: F)
+    where
+        F: FnMut(&mut T, &U),
+    {
+        IterMut::new(self)
+            .zip(Iter::new(other))
+            .for_each(|(a, b)| f(a, b))
+    }
+
+    /// Compares the list starting from `self` with the list starting
+    /// from `other` lexicographically, like slices are compared.
+    ///
+    /// If one list is a prefix of the other, the shorter one is less.
+    /// Stops at the first pair of elements that differ,
+    /// without allocating.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    /// use std::cmp::Ordering;
+    ///
+    /// let mut node0 = LinkNode::new(1);
+    /// let mut node1 = LinkNode::new(2);
+    /// node0.add(&mut node1);
+    ///
+    /// assert_eq!(node0.cmp_rings(&node1), Ordering::Less);
+    /// assert_eq!(node0.cmp_rings(&LinkNode::new(1)), Ordering::Greater);
+    /// ```
+    #[must_use]
+    pub fn cmp_rings(&self, other: &LinkNode<T>) -> Ordering
+    where
+        T: Ord,
+    {
+        Iter::new(self).cmp(Iter::new(other))
+    }
+
+    /// Same as [`LinkNode::cmp_rings`] for partially ordered elements,
+    /// returning `None` at the first pair of incomparable elements.
+    #[must_use]
+    pub fn partial_cmp_rings(&self, other: &LinkNode<T>) -> Option<Ordering>
+    where
+        T: PartialOrd,
+    {
+        Iter::new(self).partial_cmp(Iter::new(other))
+    }
+
+    /// Feeds the list starting from `self` into `state`: its length,
+    /// then each element in order, the same as hashing a slice
+    /// of the elements.
+    ///
+    /// The hash depends on the anchor: rotations of the same ring
+    /// hash differently, see [`LinkNode::hash_ring_canonical`].
+    pub fn hash_ring<H>(&self, state: &mut H)
+    where
+        T: Hash,
+        H: Hasher,
+    {
+        state.write_usize(self.len());
+        self.for_each(|data| data.hash(state));
+    }
+
+    /// Same as [`LinkNode::hash_ring`], but starts from the rotation
+    /// of the ring that is the lexicographically smallest,
+    /// so that every node of a list, or of an equal ring read from
+    /// anywhere, gives the same hash.
+    ///
+    /// Finding the smallest rotation is O(n),
+    /// and allocates a vector of n references.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    /// use std::hash::{DefaultHasher, Hasher};
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// node0.add(&mut node1);
+    ///
+    /// let hash = |node: &LinkNode<i32>, canonical: bool| {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     if canonical {
+    ///         node.hash_ring_canonical(&mut hasher);
+    ///     } else {
+    ///         node.hash_ring(&mut hasher);
+    ///     }
+    ///     hasher.finish()
+    /// };
+    /// assert_ne!(hash(&node0, false), hash(&node1, false));
+    /// assert_eq!(hash(&node0, true), hash(&node1, true));
+    /// ```
+    pub fn hash_ring_canonical<H>(&self, state: &mut H)
+    where
+        T: Hash + Ord,
+        H: Hasher,
+    {
+        let items = Iter::new(self).collect::<Vec<_>>();
+        let len = items.len();
+        // the least rotation, found by comparing candidate
+        // starting points `i` and `j` over `k` elements.
+        let (mut i, mut j, mut k) = (0, 1, 0);
+        while i < len && j < len && k < len {
+            match items[(i + k) % len].cmp(items[(j + k) % len]) {
+                Ordering::Equal => k += 1,
+                ordering => {
+                    if ordering == Ordering::Greater {
+                        i += k + 1;
+                    } else {
+                        j += k + 1;
+                    }
+                    if i == j {
+                        j += 1;
+                    }
+                    k = 0;
+                }
+            }
+        }
+        let start = i.min(j);
+        state.write_usize(len);
+        for data in items[start..].iter().chain(&items[..start]) {
+            data.hash(state);
+        }
+    }
+
+    /// Returns `true` if the first `prefix.len()` elements
+    /// going forward from `self` are equal to `prefix`.
+    ///
+    /// Returns `false` if the list is shorter than `prefix`,
+    /// and `true` if `prefix` is empty.
+    #[must_use]
+    pub fn starts_with(&self, prefix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut iter = Iter::new(self);
+        prefix.iter().all(|data| iter.next() == Some(data))
+    }
+
+    /// Returns `true` if the last `suffix.len()` elements of the list
+    /// read forward from `self`, i.e. the elements right before `self`,
+    /// are equal to `suffix`, in forward order.
+    ///
+    /// Returns `false` if the list is shorter than `suffix`,
+    /// and `true` if `suffix` is empty.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// assert!(node1.starts_with(&[1, 2]));
+    /// assert!(node1.ends_with(&[2, 0]));
+    /// ```
+    #[must_use]
+    pub fn ends_with(&self, suffix: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        let mut iter = Iter::new(self);
+        suffix
+            .iter()
+            .rev()
+            .all(|data| iter.next_back() == Some(data))
+    }
+
+    /// Walks the list starting from `self` and the list starting
+    /// from `other` in lockstep, and returns the index of the first
+    /// position where `eq` returns `false`, or where one list ends
+    /// before the other.
+    ///
+    /// Returns `None` if the lists match in both content and length.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// node0.add(&mut node1);
+    /// let other = LinkNode::new("0");
+    ///
+    /// let eq = |a: &i32, b: &&str| a.to_string() == *b;
+    /// assert_eq!(node0.mismatch(&other, eq), Some(1));
+    /// assert_eq!(node1.mismatch(&other, eq), Some(0));
+    /// ```
+    pub fn mismatch<U, F>(&self, other: &LinkNode<U>, mut eq: F) -> Option<usize>
+    where
+        F: FnMut(&T, &U) -> bool,
+    {
+        let mut iter = Iter::new(self);
+        let mut other_iter = Iter::new(other);
+        let mut index = 0;
+        loop {
+            match (iter.next(), other_iter.next()) {
+                (None, None) => return None,
+                (Some(a), Some(b)) if eq(a, b) => index += 1,
+                _ => return Some(index),
+            }
+        }
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to an immutable reference
+    /// to each element's data, stopping at the first
+    /// `ControlFlow::Break`, which is returned.
+    pub fn try_for_each<B, F>(&self, f: F) -> ControlFlow<B>
+    where
+        F: FnMut(&T) -> ControlFlow<B>,
+    {
+        Iter::new(self).try_for_each(f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to a mutable reference
+    /// to each element's data, stopping at the first
+    /// `ControlFlow::Break`, which is returned.
+    pub fn try_for_each_mut<B, F>(&mut self, f: F) -> ControlFlow<B>
+    where
+        F: FnMut(&mut T) -> ControlFlow<B>,
+    {
+        IterMut::new(self).try_for_each(f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to an immutable reference
+    /// to each element's data, stopping at the first
+    /// `ControlFlow::Break`, which is returned.
+    pub fn try_for_each_rev<B, F>(&self, f: F) -> ControlFlow<B>
+    where
+        F: FnMut(&T) -> ControlFlow<B>,
+    {
+        Iter::new_rev(self).try_for_each(f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to a mutable reference
+    /// to each element's data, stopping at the first
+    /// `ControlFlow::Break`, which is returned.
+    pub fn try_for_each_mut_rev<B, F>(&mut self, f: F) -> ControlFlow<B>
+    where
+        F: FnMut(&mut T) -> ControlFlow<B>,
+    {
+        IterMut::new_rev(self).try_for_each(f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to an immutable reference
+    /// to each element's data, stopping at the first `Err`,
+    /// which is returned.
+    ///
+    /// # Example
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(-1);
+    /// node0.add(&mut node1);
+    ///
+    /// let result = node0.try_for_each_err(|&i| u8::try_from(i).map(|_| ()));
+    /// assert!(result.is_err());
+    /// ```
+    pub fn try_for_each_err<E, F>(&self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&T) -> Result<(), E>,
+    {
+        Iter::new(self).try_for_each(f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to a mutable reference
+    /// to each element's data, stopping at the first `Err`,
+    /// which is returned.
+    pub fn try_for_each_err_mut<E, F>(&mut self, f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut T) -> Result<(), E>,
+    {
+        IterMut::new(self).try_for_each(f)
+    }
+
+    /// Applies function `f` to the data of the first element,
+    /// starting from `self` and going forward, that satisfies `predicate`,
+    /// and returns its result, or `None` if no element matches.
+    /// Stops walking as soon as a match is found.
+    ///
+    /// The data is only lent to `f`, as `self` does not borrow
+    /// the other nodes of the list.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// node0.add(&mut node1);
+    ///
+    /// assert_eq!(node0.find(|&i| i > 0, |&i| i * 10), Some(10));
+    /// ```
+    ///
+    /// A reference to the data cannot escape `f`, since it could
+    /// outlive the node it points into.
+    /// ```compile_fail
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// node0.add(&mut node1);
+    /// let found = node0.find(|&i| i == 1, |data| data);
+    /// drop(node1);
+    /// assert_eq!(found, Some(&1));
+    /// ```
+    #[inline]
+    pub fn find<P, F, R>(&self, mut predicate: P, f: F) -> Option<R>
+    where
+        P: FnMut(&T) -> bool,
+        F: FnOnce(&T) -> R,
+    {
+        Iter::new(self).find(|data| predicate(data)).map(f)
+    }
+
+    /// Same as [`LinkNode::find`], but lends a mutable reference
+    /// to the data of the matching element to `f`.
+    #[inline]
+    pub fn find_mut<P, F, R>(&mut self, mut predicate: P, f: F) -> Option<R>
+    where
+        P: FnMut(&T) -> bool,
+        F: FnOnce(&mut T) -> R,
+    {
+        IterMut::new(self).find(|data| predicate(data)).map(f)
+    }
+
+    /// Same as [`LinkNode::find`], but walks in the reverse order,
+    /// i.e. starting from the node before `self` and ending at `self`.
+    #[inline]
+    pub fn rfind<P, F, R>(&self, mut predicate: P, f: F) -> Option<R>
+    where
+        P: FnMut(&T) -> bool,
+        F: FnOnce(&T) -> R,
+    {
+        Iter::new(self).rfind(|data| predicate(data)).map(f)
+    }
+
+    /// Applies function `f` to each element, starting from `self`
+    /// and going forward, and returns the first non-`None` result.
+    /// Stops walking as soon as `f` returns `Some`.
+    #[inline]
+    pub fn find_map<U, F>(&self, f: F) -> Option<U>
+    where
+        F: FnMut(&T) -> Option<U>,
+    {
+        Iter::new(self).find_map(f)
+    }
+
+    /// Returns `true` if any element in the list satisfies `predicate`.
+    /// Stops walking at the first element that does.
+    #[inline]
+    pub fn any<P>(&self, predicate: P) -> bool
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Iter::new(self).any(predicate)
+    }
+
+    /// Returns `true` if every element in the list satisfies `predicate`.
+    /// Stops walking at the first element that does not.
+    #[inline]
+    pub fn all<P>(&self, predicate: P) -> bool
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Iter::new(self).all(predicate)
+    }
+
+    /// Returns `true` if any element in the list, including `self`,
+    /// is equal to `value`. Stops walking at the first match.
+    #[must_use]
+    #[inline]
+    pub fn contains(&self, value: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        Iter::new(self).any(|data| data == value)
+    }
+
+    /// Same as [`LinkNode::contains`], but matches elements
+    /// with `predicate`, for types without `PartialEq`.
+    #[inline]
+    pub fn contains_by<P>(&self, predicate: P) -> bool
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.any(predicate)
+    }
+
+    /// Returns `true` if the list, read as a linear sequence
+    /// starting at `self`, is sorted in ascending order.
+    ///
+    /// The wrap-around pair `(last, self)` is not checked,
+    /// and a standalone node is trivially sorted.
+    #[must_use]
+    #[inline]
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Same as [`LinkNode::is_sorted`], but checks each adjacent
+    /// pair `(current, next)` with `compare`, which returns `true`
+    /// if the pair is in order.
+    pub fn is_sorted_by<F>(&self, mut compare: F) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut iter = Iter::new(self);
+        let first = iter.next().expect("list is never empty");
+        iter.try_fold(first, |prev, next| compare(prev, next).then_some(next))
+            .is_some()
+    }
+
+    /// Returns `true` if the list is sorted in ascending order
+    /// when read from some node, not necessarily `self`,
+    /// i.e. it is a rotation of a sorted sequence.
+    ///
+    /// Unlike [`LinkNode::is_sorted`], the wrap-around pair
+    /// `(last, self)` is checked too: going around the whole ring,
+    /// at most one pair may be out of order.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(3);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// assert!(!node0.is_sorted());
+    /// assert!(node0.is_sorted_circular());
+    /// assert!(node1.is_sorted());
+    /// ```
+    #[must_use]
+    pub fn is_sorted_circular(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        let mut descents = 0;
+        self.for_each_pair(|a, b| {
+            if !matches!(a.partial_cmp(b), Some(Ordering::Less | Ordering::Equal)) {
+                descents += 1;
+            }
+        });
+        descents <= 1
+    }
+
+    /// Returns the index, counting from `self`, of the first element
+    /// not less than `value` in a list sorted from `self`,
+    /// or the length of the list if every element is less.
+    ///
+    /// This is the position where `value` can be inserted
+    /// to keep the list sorted. The list is scanned linearly,
+    /// stopping as soon as the bound is found.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(1);
+    /// let mut node1 = LinkNode::new(3);
+    /// let mut node2 = LinkNode::new(5);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// assert_eq!(node0.lower_bound(&3), 1);
+    /// assert_eq!(node0.lower_bound(&4), 2);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn lower_bound(&self, value: &T) -> usize
+    where
+        T: Ord,
+    {
+        self.lower_bound_by(|data| data.cmp(value))
+    }
+
+    /// Same as [`LinkNode::lower_bound`], but `f` compares each element
+    /// with the value searched for, returning `Ordering::Less`
+    /// while the bound is not reached.
+    pub fn lower_bound_by<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let mut index = 0;
+        for data in Iter::new(self) {
+            if f(data) != Ordering::Less {
+                break;
+            }
+            index += 1;
+        }
+        index
+    }
+
+    /// Returns `true` if two elements in the list are equal.
+    ///
+    /// This is O(n), using a hash set of references to the elements.
+    /// Requires the `std` feature, see [`LinkNode::has_duplicates_by`]
+    /// otherwise.
+    #[cfg(feature = "std")]
+    #[must_use]
+    #[inline]
+    pub fn has_duplicates(&self) -> bool
+    where
+        T: Eq + Hash,
+    {
+        self.find_duplicate(|_| ()).is_some()
+    }
+
+    /// Applies function `f` to the first element, going forward
+    /// from `self`, that is equal to an element before it,
+    /// and returns its result, or `None` if all the elements are distinct.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(1);
+    /// let mut node1 = LinkNode::new(2);
+    /// let mut node2 = LinkNode::new(1);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// let found = node0.find_duplicate(|data| std::ptr::eq(data, &*node2));
+    /// assert_eq!(found, Some(true));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn find_duplicate<F, R>(&self, f: F) -> Option<R>
+    where
+        T: Eq + Hash,
+        F: FnOnce(&T) -> R,
+    {
+        let mut seen = std::collections::HashSet::new();
+        Iter::new(self).find(|&data| !seen.insert(data)).map(f)
+    }
+
+    /// Returns `true` if `eq` returns `true` for any two elements
+    /// in the list, comparing every pair in O(n²),
+    /// for types that cannot be hashed.
+    pub fn has_duplicates_by<F>(&self, mut eq: F) -> bool
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        Iter::new(self)
+            .enumerate()
+            .any(|(i, data)| Iter::new(self).take(i).any(|prev| eq(prev, data)))
+    }
+
+    /// Applies function `f` to the data of the node `n` steps
+    /// forward from `self` and returns its result, or `None` if the
+    /// list has fewer than `n + 1` nodes. `nth(0, f)` applies `f`
+    /// to the data of `self`.
+    ///
+    /// It never wraps around the list: `n` must be less than `len()`.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// node0.add(&mut node1);
+    ///
+    /// assert_eq!(node1.nth(0, |&i| i), Some(1));
+    /// assert_eq!(node1.nth(1, |&i| i), Some(0));
+    /// assert_eq!(node1.nth(2, |&i| i), None);
+    /// ```
+    #[inline]
+    pub fn nth<F, R>(&self, n: usize, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        Iter::new(self).nth(n).map(f)
+    }
+
+    /// Same as [`LinkNode::nth`], but walks backward from `self`.
+    /// `nth_back(0, f)` applies `f` to the data of `self`.
+    #[inline]
+    pub fn nth_back<F, R>(&self, n: usize, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        Iter::new_rev(self).nth(n).map(f)
+    }
+
+    /// Same as [`LinkNode::nth`], but lends a mutable reference
+    /// to the data to `f`.
+    #[inline]
+    pub fn nth_mut<F, R>(&mut self, n: usize, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        IterMut::new(self).nth(n).map(f)
+    }
+
+    /// Same as [`LinkNode::nth_back`], but lends a mutable reference
+    /// to the data to `f`.
+    #[inline]
+    pub fn nth_back_mut<F, R>(&mut self, n: usize, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        IterMut::new_rev(self).nth(n).map(f)
+    }
+
+    /// Returns the distance going forward from `self` to the first
+    /// element that satisfies `predicate`, or `None` if none does.
+    /// Stops walking as soon as a match is found.
+    #[inline]
+    pub fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Iter::new(self).position(predicate)
+    }
+
+    /// Returns the number of steps backward from `self` to the first
+    /// element that satisfies `predicate`, or `None` if none does.
+    /// `self` is at 0 and the node before it at 1.
+    /// Stops walking as soon as a match is found.
+    #[inline]
+    pub fn rposition<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        Iter::new_rev(self).position(predicate)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to an immutable reference
+    /// to each element's data, until `f` returns `false`.
+    /// Returns the number of elements visited.
+    pub fn for_each_while<F>(&self, f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        visit_while(Iter::new(self), f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to a mutable reference
+    /// to each element's data, until `f` returns `false`.
+    /// Returns the number of elements visited.
+    pub fn for_each_while_mut<F>(&mut self, f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        visit_while(IterMut::new(self), f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to an immutable reference
+    /// to each element's data, until `f` returns `false`.
+    /// Returns the number of elements visited.
+    pub fn for_each_while_rev<F>(&self, f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        visit_while(Iter::new_rev(self), f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to a mutable reference
+    /// to each element's data, until `f` returns `false`.
+    /// Returns the number of elements visited.
+    pub fn for_each_while_mut_rev<F>(&mut self, f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        visit_while(IterMut::new_rev(self), f)
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to an immutable reference
+    /// to each element's data, until `f` returns `true`.
+    /// Returns the number of elements visited.
+    pub fn for_each_until<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        visit_while(Iter::new(self), |data| !f(data))
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// and applies function `f` to a mutable reference
+    /// to each element's data, until `f` returns `true`.
+    /// Returns the number of elements visited.
+    pub fn for_each_until_mut<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        visit_while(IterMut::new(self), |data| !f(data))
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to an immutable reference
+    /// to each element's data, until `f` returns `true`.
+    /// Returns the number of elements visited.
+    pub fn for_each_until_rev<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        visit_while(Iter::new_rev(self), |data| !f(data))
+    }
+
+    /// Iterates over each element in the list starting from `self`
+    /// in reverse order and applies function `f` to a mutable reference
+    /// to each element's data, until `f` returns `true`.
+    /// Returns the number of elements visited.
+    pub fn for_each_until_mut_rev<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        visit_while(IterMut::new_rev(self), |data| !f(data))
+    }
+
+    /// Applies function `f` to the element of the list with the
+    /// maximum value with respect to `compare`, walking forward
+    /// from `self`, and returns its result.
+    /// On ties, the first element encountered is chosen.
+    ///
+    /// The element is only lent to `f`, as `self` does not borrow
+    /// the other nodes of the list.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new((1, 'a'));
+    /// let mut node1 = LinkNode::new((2, 'b'));
+    /// node0.add(&mut node1);
+    ///
+    /// assert_eq!(node0.max_by(|a, b| a.0.cmp(&b.0), |&(_, c)| c), 'b');
+    /// ```
+    pub fn max_by<C, F, R>(&self, mut compare: C, f: F) -> R
+    where
+        C: FnMut(&T, &T) -> Ordering,
+        F: FnOnce(&T) -> R,
+    {
+        self.extreme_by(|data, max| compare(data, max) == Ordering::Greater, f)
+    }
+
+    /// Same as [`LinkNode::max_by`], for the element with the
+    /// minimum value with respect to `compare`.
+    /// On ties, the first element encountered is chosen.
+    pub fn min_by<C, F, R>(&self, mut compare: C, f: F) -> R
+    where
+        C: FnMut(&T, &T) -> Ordering,
+        F: FnOnce(&T) -> R,
+    {
+        self.extreme_by(|data, min| compare(data, min) == Ordering::Less, f)
+    }
+
+    /// Same as [`LinkNode::max_by`], for the element that gives
+    /// the maximum value of `key`.
+    /// On ties, the first element encountered is chosen.
+    pub fn max_by_key<K, G, F, R>(&self, mut key: G, f: F) -> R
+    where
+        K: Ord,
+        G: FnMut(&T) -> K,
+        F: FnOnce(&T) -> R,
+    {
+        self.max_by(|a, b| key(a).cmp(&key(b)), f)
+    }
+
+    /// Same as [`LinkNode::max_by`], for the element that gives
+    /// the minimum value of `key`.
+    /// On ties, the first element encountered is chosen.
+    pub fn min_by_key<K, G, F, R>(&self, mut key: G, f: F) -> R
+    where
+        K: Ord,
+        G: FnMut(&T) -> K,
+        F: FnOnce(&T) -> R,
+    {
+        self.min_by(|a, b| key(a).cmp(&key(b)), f)
+    }
+
+    /// Applies function `f` to the element that would be at position `k`
+    /// if the list were sorted with `compare` and returns its result,
+    /// or `None` if `k` is not less than the length of the list.
+    /// `k = 0` gives a minimum.
+    ///
+    /// The list is left untouched: references to the elements are
+    /// collected into a vector, which is partially sorted with
+    /// `slice::select_nth_unstable_by` in O(n) on average.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(3);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// let median = node0.select_nth_by(1, |a, b| a.cmp(b), |&i| i);
+    /// assert_eq!(median, Some(2));
+    /// ```
+    pub fn select_nth_by<C, F, R>(&self, k: usize, mut compare: C, f: F) -> Option<R>
+    where
+        C: FnMut(&T, &T) -> Ordering,
+        F: FnOnce(&T) -> R,
+    {
+        let mut items = Iter::new(self).collect::<Vec<_>>();
+        if k >= items.len() {
+            return None;
+        }
+        Some(f(items.select_nth_unstable_by(k, |a, b| compare(a, b)).1))
+    }
+
+    /// Same as [`LinkNode::select_nth_by`], comparing the keys
+    /// extracted from the elements by `key`.
+    pub fn select_nth_by_key<K, G, F, R>(&self, k: usize, mut key: G, f: F) -> Option<R>
+    where
+        K: Ord,
+        G: FnMut(&T) -> K,
+        F: FnOnce(&T) -> R,
+    {
+        self.select_nth_by(k, |a, b| key(a).cmp(&key(b)), f)
+    }
+
+    /// Applies `f` to the first element for which `replaces` holds
+    /// against every element before it that it was compared to.
+    #[inline(always)]
+    fn extreme_by<P, F, R>(&self, mut replaces: P, f: F) -> R
+    where
+        P: FnMut(&T, &T) -> bool,
+        F: FnOnce(&T) -> R,
+    {
+        let mut iter = Iter::new(self);
+        let first = iter.next().unwrap();
+        f(iter.fold(
+            first,
+            |best, data| {
+                if replaces(data, best) {
+                    data
+                } else {
+                    best
+                }
+            },
+        ))
+    }
+
+    /// Returns `true` if `a` and `b` are the same node,
+    /// by comparing their addresses rather than their data.
+    #[must_use]
+    #[inline]
+    pub fn ptr_eq(a: &LinkNode<T>, b: &LinkNode<T>) -> bool {
+        a.inner == b.inner
+    }
+
+    /// Returns `true` if `self` and `other` are in the same list.
+    ///
+    /// This walks the list of `self` and is O(n).
+    #[must_use]
+    #[inline]
+    pub fn same_list(&self, other: &LinkNode<T>) -> bool {
+        unsafe { ListHead::reaches(self.head(), other.head()) }
+    }
+
+    /// Folds every element in the list starting from `self` into
+    /// an accumulator by applying function `f`, stopping at the first
+    /// error, which is returned.
+    pub fn try_fold<B, E, F>(&self, init: B, f: F) -> Result<B, E>
+    where
+        F: FnMut(B, &T) -> Result<B, E>,
+    {
+        Iter::new(self).try_fold(init, f)
+    }
+
+    /// Folds every element in the list starting from `self`
+    /// into an accumulator by applying function `f`.
+    pub fn fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        Iter::new(self).fold(init, f)
+    }
+
+    /// Reduces the list to a single value: the accumulator is seeded
+    /// with `init_map` applied to the data of `self`, then every other
+    /// element is folded in by applying function `f`, going forward.
+    ///
+    /// Unlike `Iterator::reduce`, this needs no `Option`:
+    /// a list always has at least one element.
+    ///
+    /// # Example
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new("a");
+    /// let mut node1 = LinkNode::new("b");
+    /// node0.add(&mut node1);
+    ///
+    /// let joined = node1.reduce(|s| s.to_string(), |acc, s| acc + "-" + s);
+    /// assert_eq!(joined, "b-a");
+    /// ```
+    pub fn reduce<U, M, F>(&self, init_map: M, f: F) -> U
+    where
+        M: FnOnce(&T) -> U,
+        F: FnMut(U, &T) -> U,
+    {
+        let mut iter = Iter::new(self);
+        let init = init_map(iter.next().unwrap());
+        iter.fold(init, f)
+    }
+
+    /// Folds every element in the list into an accumulator
+    /// by applying function `f`, in the reverse order of `fold`,
+    /// i.e. starting from the node before `self` and ending at `self`.
+    pub fn rfold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        Iter::new(self).rfold(init, f)
+    }
+
+    /// Walks the list starting from `self` for `k` full laps
+    /// and applies function `f` to an immutable reference
+    /// to each element's data, visiting every element `k` times.
+    pub fn for_each_cycles<F>(&self, k: usize, mut f: F)
+    where
+        F: FnMut(&T),
+    {
+        let start = self.head();
+        unsafe { ListHead::walk_cycles(start, k, |this| f(ListHead::data_ptr(this).as_ref())) }
+    }
+
+    /// Walks the list starting from `self` for `k` full laps
+    /// and applies function `f` to a mutable reference
+    /// to each element's data, visiting every element `k` times.
+    pub fn for_each_cycles_mut<F>(&mut self, k: usize, mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        let start = self.head();
+        unsafe { ListHead::walk_cycles(start, k, |this| f(ListHead::data_ptr(this).as_mut())) }
+    }
+
+    /// Iterates over each element in the list except `self`,
+    /// starting from the node after `self`, and applies function `f`
+    /// to a mutable reference to each element's data.
+    /// Every node for which `f` returns `true` is removed from the list,
+    /// turning it into a standalone element.
+    ///
+    /// `self` itself is never visited nor removed,
+    /// so it remains a valid anchor of the list.
+    /// Returns the number of nodes removed.
+    pub fn for_each_detach<F>(&mut self, mut f: F) -> usize
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let start = self.head();
+        let mut detached = 0;
+        let mut this = unsafe { ListHead::next_ptr(start) };
+        while this != start {
+            // the next pointer is read before `this` may be detached
+            let next = unsafe { ListHead::next_ptr(this) };
+            let start_guard = Snapshot::new(start);
+            let guard = Snapshot::new(this);
+            let detach = f(unsafe { ListHead::data_ptr(this).as_mut() });
+            start_guard.check();
+            guard.check();
+            if detach {
+                unsafe {
+                    ListHead::delist(this);
+                    ListHead::init_head(this);
+                }
+                detached += 1;
+            }
+            this = next;
+        }
+        detached
+    }
+
+    /// Removes consecutive elements that `same` considers equal,
+    /// walking forward from `self`: each element is compared with
+    /// the last element kept, as `same(element, kept)`, and its node
+    /// is removed from the list if `same` returns `true`.
+    ///
+    /// The list is read as a linear sequence starting at `self`,
+    /// so `self` is always kept, and the last element
+    /// is not compared with `self`.
+    ///
+    /// The removed nodes are owned by their own handles,
+    /// so they are not dropped but turned into standalone elements.
+    /// Use [`List::dedup_by`] to drop them.
+    pub fn dedup_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let start = self.head();
+        let mut kept = start;
+        let mut this = unsafe { ListHead::next_ptr(start) };
+        while this != start {
+            let next = unsafe { ListHead::next_ptr(this) };
+            let guard = Snapshot::new(this);
+            let duplicate = unsafe {
+                same(
+                    ListHead::data_ptr(this).as_ref(),
+                    ListHead::data_ptr(kept).as_ref(),
+                )
+            };
+            guard.check();
+            if duplicate {
+                unsafe {
+                    ListHead::delist(this);
+                    ListHead::init_head(this);
+                }
+            } else {
+                kept = this;
+            }
+            this = next;
+        }
+    }
+
+    /// Keeps only the elements for which `keep` returns `true`,
+    /// visiting every element once, forward from `self`.
+    ///
+    /// The other nodes are owned by their own handles,
+    /// so the removed nodes are not dropped but turned into
+    /// standalone elements. Use [`List::retain`] to drop them.
+    ///
+    /// `self` is removed too if its element is rejected,
+    /// in which case it ends up standalone, and the kept nodes stay
+    /// linked together in their order, reachable from any of them.
+    ///
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// node1.retain(|&i| i % 2 == 0);
+    /// assert!(node1.is_standalone());
+    /// assert_eq!(node0.to_vec(), [0, 2]);
+    /// ```
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let keep_self = keep(self);
+        self.for_each_detach(|data| !keep(data));
+        if !keep_self {
+            self.take();
+        }
+    }
+
+    /// Iterates over each element in the list starting `n` nodes
+    /// after `self`, up to but excluding `self`, and applies function `f`
+    /// to an immutable reference to each element's data.
+    /// Does nothing if `n` is not smaller than the length of the list.
+    pub fn for_each_from<F>(&self, n: usize, f: F)
+    where
+        F: FnMut(&T),
+    {
+        Iter::new(self).skip(n).for_each(f)
+    }
+
+    /// Iterates over each element in the list starting `n` nodes
+    /// after `self`, up to but excluding `self`, and applies function `f`
+    /// to a mutable reference to each element's data.
+    /// Does nothing if `n` is not smaller than the length of the list.
+    pub fn for_each_from_mut<F>(&mut self, n: usize, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        IterMut::new(self).skip(n).for_each(f)
+    }
+
+    /// Iterates over each element in the list in reverse order
+    /// starting `n` nodes before `self`, up to but excluding `self`,
+    /// and applies function `f` to an immutable reference
+    /// to each element's data.
+    /// Does nothing if `n` is not smaller than the length of the list.
+    pub fn for_each_from_rev<F>(&self, n: usize, f: F)
+    where
+        F: FnMut(&T),
+    {
+        Iter::new_rev(self).skip(n).for_each(f)
+    }
+
+    /// Iterates over each element in the list in reverse order
+    /// starting `n` nodes before `self`, up to but excluding `self`,
+    /// and applies function `f` to a mutable reference
+    /// to each element's data.
+    /// Does nothing if `n` is not smaller than the length of the list.
+    pub fn for_each_from_mut_rev<F>(&mut self, n: usize, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        IterMut::new_rev(self).skip(n).for_each(f)
+    }
+
+    /// Same as [`LinkNode::for_each`], but prefetches the next node
+    /// into the cache before applying `f` to the current one,
+    /// hiding some of the latency of chasing pointers in long lists
+    /// scattered across the heap.
+    ///
+    /// Prefetching is only done on x86_64. On other targets
+    /// this is the plain loop of `for_each`.
+    pub fn for_each_prefetch<F>(&self, mut f: F)
+    where
+        F: FnMut(&T),
+    {
+        let start = self.head();
+        let start_guard = Snapshot::new(start);
+        let mut this = start;
+        loop {
+            let next = unsafe { ListHead::next_ptr(this) };
+            prefetch(unsafe { ListHead::data_ptr(next) }.as_ptr());
+            prefetch(next.as_ptr());
+            let guard = Snapshot::new(this);
+            f(unsafe { ListHead::data_ptr(this).as_ref() });
+            start_guard.check();
+            guard.check();
+            if next == start {
+                break;
+            }
+            this = next;
+        }
+    }
+
+    /// Drives `visitor` over the list, starting from this node.
+    ///
+    /// Calls [`RingVisitor::begin`], then [`RingVisitor::visit`]
+    /// on each item until it breaks, then [`RingVisitor::finish`].
+    /// Returns `ControlFlow::Break` if the visitor stopped early.
+    ///
+    /// # Example
+    /// ```
+    /// use cdlist::{Collector, LinkNode};
+    ///
+    /// let mut node0 = LinkNode::new(0);
+    /// let mut node1 = LinkNode::new(1);
+    /// let mut node2 = LinkNode::new(2);
+    /// node0.add(&mut node2);
+    /// node0.add(&mut node1);
+    ///
+    /// let mut collector = Collector::with_limit(2);
+    /// assert!(node0.accept(&mut collector).is_break());
+    /// assert_eq!(collector.items(), &[0, 1]);
+    /// ```
+    pub fn accept<V>(&self, visitor: &mut V) -> ControlFlow<()>
+    where
+        V: RingVisitor<T> + ?Sized,
+    {
+        visitor.begin(None);
+        let flow = Iter::new(self)
+            .enumerate()
+            .try_for_each(|(i, data)| visitor.visit(i, data));
+        visitor.finish();
+        flow
+    }
+
+    /// Same as [`LinkNode::accept`], with mutable access to the items.
+    pub fn accept_mut<V>(&mut self, visitor: &mut V) -> ControlFlow<()>
+    where
+        V: RingVisitorMut<T> + ?Sized,
+    {
+        visitor.begin(None);
+        let flow = IterMut::new(self)
+            .enumerate()
+            .try_for_each(|(i, data)| visitor.visit(i, data));
+        visitor.finish();
+        flow
+    }
+
+    /// Leaks the node, returning a pointer to its list head,
+    /// which stays in its list.
+    #[inline(always)]
+    fn into_raw(self) -> NonNull<ListHead<T>> {
+        ManuallyDrop::new(self).head()
+    }
+
+    /// Takes back ownership of a node leaked by `into_raw`.
+    #[inline(always)]
+    unsafe fn from_raw(list: NonNull<ListHead<T>>) -> Self {
+        let inner = list.as_ptr().byte_offset(ListHead::<T>::offset());
+        Self {
+            inner: NonNull::new_unchecked(inner.cast::<Inner<T>>()),
+            marker: PhantomData,
+        }
+    }
+
+    /// Applies function `f` to each element in the list starting
+    /// from `self`, and collects the results in order
+    /// into a new, independent [`List`].
+    ///
+    /// # Example
+    /// ```
+    /// use cdlist::LinkNode;
+    ///
+    /// let mut node0 = LinkNode::new(1);
+    /// let mut node1 = LinkNode::new(2);
+    /// node0.add(&mut node1);
+    ///
+    /// let list = node1.map(|&i| i.to_string());
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), ["2", "1"]);
+    /// ```
+    #[must_use]
+    pub fn map<U, F>(&self, mut f: F) -> List<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        let mut list = List::new();
+        self.for_each(|data| list.push_back(f(data)));
+        list
+    }
+
+    /// Returns a pointer to the list head of `self`, derived from
+    /// the pointer to the whole `Inner<T>` so that the data can be
+    /// reached back from it.
+    #[inline(always)]
+    pub(crate) fn head(&self) -> NonNull<ListHead<T>> {
+        unsafe { NonNull::new_unchecked(ptr::addr_of_mut!((*self.inner.as_ptr()).list)) }
+    }
+}
+
+impl<T> DerefMut for LinkNode<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { &mut (*self.inner.as_ptr()).data }
+    }
+}
+
+impl<T> Deref for LinkNode<T> {
+    type Target = T;
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &(*self.inner.as_ptr()).data }
+    }
+}
+
+/// Creates a new standalone node, same as [`LinkNode::new`].
+impl<T> From<T> for LinkNode<T> {
+    #[inline]
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+/// Creates a new standalone node holding `T::default()`.
+impl<T: Default> Default for LinkNode<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Formats the list starting from the node, e.g. `[0, 1, 2]`.
+impl<T: Debug> Debug for LinkNode<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(Iter::new(self)).finish()
+    }
+}
+
+/// Compares the lists walked forward from each node element by element.
+///
+/// Two lists are equal if they have the same length and the same
+/// sequence of data, whether or not they are the same list.
+/// This is O(n).
+///
+/// The comparison starts at the given nodes, so it depends on where
+/// each list is read from: the same ring read from two different
+/// nodes is usually not equal to itself.
+///
+/// ```
+/// use cdlist::LinkNode;
+///
+/// let mut node0 = LinkNode::new(0);
+/// let mut node1 = LinkNode::new(1);
+/// node0.add(&mut node1);
+///
+/// let mut other0 = LinkNode::new(0);
+/// let mut other1 = LinkNode::new(1);
+/// other0.add(&mut other1);
+///
+/// assert_eq!(node0, other0);
+/// assert_ne!(node0, node1);
+/// ```
+impl<T: PartialEq> PartialEq for LinkNode<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Iter::new(self).eq(Iter::new(other))
+    }
+}
+
+impl<T: Eq> Eq for LinkNode<T> {}
+
+impl<T> Drop for LinkNode<T> {
+    fn drop(&mut self) {
+        unsafe {
+            ListHead::delist(self.head());
+            drop(Inner::release(self.inner));
+        }
+    }
+}
+
+/// Hints the CPU to load the cache line at `ptr`.
+#[inline(always)]
+fn prefetch<P>(ptr: *const P) {
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+        _mm_prefetch::<_MM_HINT_T0>(ptr.cast());
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = ptr;
+}
+
+/// Applies `f` to each item of `iter` until it returns `false`,
+/// and returns the number of items visited.
+#[inline(always)]
+fn visit_while<I, F>(iter: I, mut f: F) -> usize
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> bool,
+{
+    let mut visited = 0;
+    for item in iter {
+        visited += 1;
+        if !f(item) {
+            break;
+        }
+    }
+    visited
+}
+
+/// Every method takes list heads as raw pointers derived from the
+/// allocation of their `Inner<T>`, never from references to the
+/// `ListHead` itself, so that the pointers stored in the links keep
+/// provenance over the whole `Inner<T>`, and `data_ptr` can step
+/// back from a list head to its data.
+impl<T> ListHead<T> {
+    /// Creates a list head with uninitialized links,
+    /// to be initialized with `init_head` once it is in place.
+    #[inline(always)]
+    fn new() -> Self {
+        Self {
+            prev: MaybeUninit::uninit(),
+            next: MaybeUninit::uninit(),
+            #[cfg(feature = "debug-guard")]
+            generation: Cell::new(0),
+            dtype: PhantomData,
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn set_next(this: NonNull<ListHead<T>>, next: NonNull<ListHead<T>>) {
+        ptr::addr_of_mut!((*this.as_ptr()).next).write(MaybeUninit::new(next));
+        Self::bump(this);
+    }
+
+    #[inline(always)]
+    unsafe fn set_prev(this: NonNull<ListHead<T>>, prev: NonNull<ListHead<T>>) {
+        ptr::addr_of_mut!((*this.as_ptr()).prev).write(MaybeUninit::new(prev));
+        Self::bump(this);
+    }
+
+    /// Marks the links of `this` as changed, see `guard`.
+    #[inline(always)]
+    unsafe fn bump(this: NonNull<ListHead<T>>) {
+        #[cfg(feature = "debug-guard")]
+        {
+            let generation = &(*this.as_ptr()).generation;
+            generation.set(generation.get().wrapping_add(1));
+        }
+        #[cfg(not(feature = "debug-guard"))]
+        let _ = this;
+    }
+
+    /// Initializes the list head, setting the previous and
+    /// next pointers to point to itself, effectively creating an empty list.
+    #[inline(always)]
+    unsafe fn init_head(this: NonNull<ListHead<T>>) {
+        Self::set_prev(this, this);
+        Self::set_next(this, this);
+    }
+
+    /// Returns `true` if the next pointer points back to `this`.
+    #[inline(always)]
+    unsafe fn is_standalone(this: NonNull<ListHead<T>>) -> bool {
+        Self::next_ptr(this) == this
+    }
+
+    /// Removes the current node from its list by updating the
+    /// previous and next nodes to point to each other.
+    /// This method leaves the current node in an inconsistent state
+    /// and should be followed by reinsertion into a list using `add` or
+    /// resetting the pointers using `init_head`.
+    #[inline(always)]
+    unsafe fn delist(this: NonNull<ListHead<T>>) {
+        let prev = Self::prev_ptr(this);
+        let next = Self::next_ptr(this);
+        Self::set_next(prev, next);
+        Self::set_prev(next, prev);
+    }
+
+    /// Inserts `other` between `this` and the node currently following `this`.
+    /// Assumes `other` is not part of any list.
+    #[inline(always)]
+    unsafe fn add(this: NonNull<ListHead<T>>, other: NonNull<ListHead<T>>) {
+        let next = Self::next_ptr(this);
+        debug_assert!(
+            Self::prev_ptr(next) == this,
+            "list is corrupted: the next node does not link back"
+        );
+
+        Self::set_prev(other, this);
+        Self::set_next(other, next);
+        Self::set_prev(next, other);
+        Self::set_next(this, other);
+    }
+
+    /// Inserts `other` between the node currently preceding `this` and `this`.
+    /// Assumes `other` is not part of any list.
+    #[inline(always)]
+    unsafe fn add_before(this: NonNull<ListHead<T>>, other: NonNull<ListHead<T>>) {
+        let prev = Self::prev_ptr(this);
+        debug_assert!(
+            Self::next_ptr(prev) == this,
+            "list is corrupted: the previous node does not link back"
+        );
+
+        Self::set_next(other, this);
+        Self::set_prev(other, prev);
+        Self::set_next(prev, other);
+        Self::set_prev(this, other);
+    }
+
+    /// Inserts the whole list of `other` between `this` and the node
+    /// currently following `this`, with `other` right after `this`.
+    /// Assumes `this` and `other` are in different lists.
+    #[inline(always)]
+    unsafe fn splice(this: NonNull<ListHead<T>>, other: NonNull<ListHead<T>>) {
+        let next = Self::next_ptr(this);
+        let other_last = Self::prev_ptr(other);
+
+        Self::set_next(this, other);
+        Self::set_prev(other, this);
+        Self::set_next(other_last, next);
+        Self::set_prev(next, other_last);
+    }
+
+    /// Splits the list `this` and `other` belong to into two lists:
+    /// one from `this` up to but excluding `other`,
+    /// and one from `other` up to but excluding `this`.
+    /// Assumes `this` and `other` are distinct nodes in the same list.
+    #[inline(always)]
+    unsafe fn split(this: NonNull<ListHead<T>>, other: NonNull<ListHead<T>>) {
+        let this_prev = Self::prev_ptr(this);
+        let other_prev = Self::prev_ptr(other);
+
+        Self::set_prev(this, other_prev);
+        Self::set_next(other_prev, this);
+        Self::set_prev(other, this_prev);
+        Self::set_next(this_prev, other);
+    }
+
+    /// Exchanges the positions of `this` and `other`, which may be
+    /// in the same list or in different lists. Assumes they are distinct.
+    #[inline(always)]
+    unsafe fn swap(this: NonNull<ListHead<T>>, other: NonNull<ListHead<T>>) {
+        let this_prev = Self::prev_ptr(this);
+        let this_next = Self::next_ptr(this);
+        let other_prev = Self::prev_ptr(other);
+        let other_next = Self::next_ptr(other);
+
+        if this_next == other {
+            // `this` right before `other`, including a list of two
+            Self::delist(other);
+            Self::add_before(this, other);
+            return;
+        }
+        if other_next == this {
+            Self::delist(this);
+            Self::add_before(other, this);
+            return;
+        }
+
+        // a standalone node links to itself, and must then
+        // link to the node taking its place instead
+        let swap_self = |ptr: NonNull<ListHead<T>>, from, to| if ptr == from { to } else { ptr };
+        Self::set_next(this_prev, other);
+        Self::set_prev(this_next, other);
+        Self::set_next(other_prev, this);
+        Self::set_prev(other_next, this);
+        Self::set_prev(this, swap_self(other_prev, other, this));
+        Self::set_next(this, swap_self(other_next, other, this));
+        Self::set_prev(other, swap_self(this_prev, this, other));
+        Self::set_next(other, swap_self(this_next, this, other));
+    }
+
+    /// Sorts the list `start` belongs to with a stable merge sort,
+    /// relinking the nodes without moving their data,
+    /// and returns the smallest node, which is now the head of the list.
+    ///
+    /// This is the bottom-up merge sort by Simon Tatham:
+    /// every pass merges pairs of sorted runs of `run` nodes,
+    /// read from the head of the previous pass, until a pass
+    /// does a single merge.
+    unsafe fn sort_by<F>(start: NonNull<ListHead<T>>, mut compare: F) -> NonNull<ListHead<T>>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut head = start;
+        let mut run = 1;
+        loop {
+            let old_head = head;
+            let step = |this| {
+                let next = Self::next_ptr(this);
+                (next != old_head).then_some(next)
+            };
+            let mut left = Some(old_head);
+            let mut new_head = None;
+            let mut tail: Option<NonNull<ListHead<T>>> = None;
+            let mut merges = 0;
+            while let Some(left_start) = left {
+                merges += 1;
+                let mut left_len = 0;
+                let mut right = Some(left_start);
+                while let Some(this) = right {
+                    if left_len == run {
+                        break;
+                    }
+                    left_len += 1;
+                    right = step(this);
+                }
+                let mut right_len = run;
+                loop {
+                    let take_left = match (left, right) {
+                        (Some(l), Some(r)) if left_len > 0 && right_len > 0 => {
+                            // `compare` may panic halfway through a merge
+                            let guard = SortGuard {
+                                head: new_head,
+                                tail,
+                                left: l,
+                                left_len,
+                                right: r,
+                                end: old_head,
+                            };
+                            let order =
+                                compare(Self::data_ptr(l).as_ref(), Self::data_ptr(r).as_ref());
+                            mem::forget(guard);
+                            order != Ordering::Greater
+                        }
+                        _ if left_len > 0 => true,
+                        (_, Some(_)) if right_len > 0 => false,
+                        _ => break,
+                    };
+                    let this = if take_left {
+                        left_len -= 1;
+                        let this = left.unwrap_unchecked();
+                        left = step(this);
+                        this
+                    } else {
+                        right_len -= 1;
+                        let this = right.unwrap_unchecked();
+                        right = step(this);
+                        this
+                    };
+                    match tail {
+                        Some(tail) => {
+                            Self::set_next(tail, this);
+                            Self::set_prev(this, tail);
+                        }
+                        None => new_head = Some(this),
+                    }
+                    tail = Some(this);
+                }
+                left = right;
+            }
+            head = new_head.unwrap_unchecked();
+            let tail = tail.unwrap_unchecked();
+            Self::set_next(tail, head);
+            Self::set_prev(head, tail);
+            if merges <= 1 {
+                return head;
+            }
+            run *= 2;
+        }
+    }
+
+    /// Swaps the previous and next links of every node in the list.
+    #[inline(always)]
+    unsafe fn reverse(start: NonNull<ListHead<T>>) {
+        let mut this = start;
+        loop {
+            let next = Self::next_ptr(this);
+            let prev = Self::prev_ptr(this);
+            ptr::addr_of_mut!((*this.as_ptr()).next).write(MaybeUninit::new(prev));
+            ptr::addr_of_mut!((*this.as_ptr()).prev).write(MaybeUninit::new(next));
+            Self::bump(this);
+            if next == start {
+                break;
+            }
+            this = next;
+        }
+    }
+
+    /// Walks the list starting from `start` for `k` full laps,
+    /// applying `f` to each node. A lap ends when the walk
+    /// wraps around to `start`.
+    #[inline(always)]
+    unsafe fn walk_cycles<F>(start: NonNull<ListHead<T>>, k: usize, mut f: F)
+    where
+        F: FnMut(NonNull<ListHead<T>>),
+    {
+        let start_guard = Snapshot::new(start);
+        let mut laps = 0;
+        let mut this = start;
+        while laps < k {
+            let guard = Snapshot::new(this);
+            f(this);
+            start_guard.check();
+            guard.check();
+            this = Self::next_ptr(this);
+            if this == start {
+                laps += 1;
+            }
+        }
+    }
+
+    /// Returns `true` if `other` can be reached
+    /// by walking forward from `start`.
+    #[inline(always)]
+    unsafe fn reaches(start: NonNull<ListHead<T>>, other: NonNull<ListHead<T>>) -> bool {
+        let mut this = start;
+        loop {
+            if this == other {
+                return true;
+            }
+            this = Self::next_ptr(this);
+            if this == start {
+                return false;
+            }
+        }
+    }
+
+    /// Walks the list once starting from `start`, following `step`,
+    /// and applies `f` to each node.
+    #[inline(always)]
+    unsafe fn walk<F>(
+        start: NonNull<ListHead<T>>,
+        step: unsafe fn(NonNull<ListHead<T>>) -> NonNull<ListHead<T>>,
+        mut f: F,
+    ) where
+        F: FnMut(NonNull<ListHead<T>>),
+    {
+        let start_guard = Snapshot::new(start);
+        let mut this = start;
+        loop {
+            let guard = Snapshot::new(this);
+            f(this);
+            start_guard.check();
+            guard.check();
+            let next = step(this);
+            if next == start {
+                break;
+            }
+            this = next;
+        }
+    }
+
+    #[inline(always)]
+    unsafe fn for_each<F>(start: NonNull<ListHead<T>>, mut f: F)
+    where
+        F: FnMut(&T),
+    {
+        Self::walk(start, Self::next_ptr, |this| {
+            f(Self::data_ptr(this).as_ref())
+        })
+    }
+
+    #[inline(always)]
+    unsafe fn for_each_mut<F>(start: NonNull<ListHead<T>>, mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        Self::walk(start, Self::next_ptr, |this| {
+            f(Self::data_ptr(this).as_mut())
+        })
+    }
+
+    #[inline(always)]
+    unsafe fn for_each_rev<F>(start: NonNull<ListHead<T>>, mut f: F)
+    where
+        F: FnMut(&T),
+    {
+        Self::walk(start, Self::prev_ptr, |this| {
+            f(Self::data_ptr(this).as_ref())
+        })
+    }
+
+    #[inline(always)]
+    unsafe fn for_each_rev_mut<F>(start: NonNull<ListHead<T>>, mut f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        Self::walk(start, Self::prev_ptr, |this| {
+            f(Self::data_ptr(this).as_mut())
+        })
+    }
+
+    /// Returns a raw pointer to the data of the `Inner<T>` struct
+    /// associated with the list head `this`, without creating
+    /// any intermediate reference.
+    #[inline(always)]
+    unsafe fn data_ptr(this: NonNull<ListHead<T>>) -> NonNull<T> {
+        let inner = this.as_ptr().byte_offset(Self::offset()).cast::<Inner<T>>();
+        NonNull::new_unchecked(ptr::addr_of_mut!((*inner).data))
+    }
+
+    /// Reads the next pointer of the list head `this`
+    /// without creating any intermediate reference.
+    #[inline(always)]
+    unsafe fn next_ptr(this: NonNull<ListHead<T>>) -> NonNull<ListHead<T>> {
+        ptr::addr_of!((*this.as_ptr()).next).read().assume_init()
+    }
+
+    /// Reads the previous pointer of the list head `this`
+    /// without creating any intermediate reference.
+    #[inline(always)]
+    unsafe fn prev_ptr(this: NonNull<ListHead<T>>) -> NonNull<ListHead<T>> {
+        ptr::addr_of!((*this.as_ptr()).prev).read().assume_init()
+    }
+
+    /// The compiler will compile this into an inlined constant
+    /// even without inline const feature.
+    #[inline(always)]
+    const fn offset() -> isize {
+        -(offset_of!(Inner<T>, list) as isize)
+    }
+}
+
+/// Relinks the nodes into a ring if the comparison
+/// of [`ListHead::sort_by`] panics in the middle of a merge.
+///
+/// The nodes are then in three chains: the merged nodes from `head`
+/// to `tail`, if any, the `left_len` nodes left in the left run,
+/// and the nodes from `right` up to `end`, which are the rest of
+/// the right run followed by the runs not merged yet.
+/// The unmerged nodes still have their next links from before the pass.
+struct SortGuard<T> {
+    head: Option<NonNull<ListHead<T>>>,
+    tail: Option<NonNull<ListHead<T>>>,
+    left: NonNull<ListHead<T>>,
+    left_len: usize,
+    right: NonNull<ListHead<T>>,
+    end: NonNull<ListHead<T>>,
+}
+
+impl<T> Drop for SortGuard<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let (head, mut tail) = match (self.head, self.tail) {
+                (Some(head), Some(tail)) => (head, tail),
+                _ => (self.left, self.left),
+            };
+            let mut append = |this| {
+                if this != tail {
+                    ListHead::set_next(tail, this);
+                    ListHead::set_prev(this, tail);
+                    tail = this;
+                }
+            };
+            let mut this = self.left;
+            for _ in 0..self.left_len {
+                let next = ListHead::next_ptr(this);
+                append(this);
+                this = next;
+            }
+            let mut this = self.right;
+            loop {
+                let next = ListHead::next_ptr(this);
+                append(this);
+                if next == self.end {
+                    break;
+                }
+                this = next;
+            }
+            ListHead::set_next(tail, head);
+            ListHead::set_prev(head, tail);
+        }
     }
 }
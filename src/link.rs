@@ -0,0 +1,283 @@
+//! Intrusive links embedded in user structs, so that the same
+//! value can be on several lists at once.
+//!
+//! A struct declares one [`Link`] field per list it can be on,
+//! and one [`Adapter`] per field, usually with [`link_adapter!`](crate::link_adapter),
+//! tells the list operations where to find that field.
+//! Like `LinkNode`, there is no separate list head:
+//! any value on a list gives access to the whole list.
+//!
+//! The links do not reuse the `ListHead` of `LinkNode`. A `ListHead`
+//! lives in a heap node and is standalone when it points to itself,
+//! which a `Link` cannot do: it is created by value and only gets a
+//! stable address once pinned. So a standalone `Link` has no pointers
+//! instead, and its pointers are `Cell`s, because a pinned value
+//! is only reached through shared references.
+//!
+//! Nothing ties a value to its place while it is linked, so adding
+//! a value to a list is `unsafe`: it must not move until it is removed
+//! or dropped. Pinning a type which is not `Unpin` ensures that,
+//! and a `Link` field makes the struct `!Unpin` unless it opts out.
+//!
+//! # Example
+//! ```
+//! use cdlist::{link_adapter, Adapter, Link};
+//! use std::pin::pin;
+//!
+//! struct Task {
+//!     id: u32,
+//!     ready: Link,
+//!     timer: Link,
+//! }
+//!
+//! link_adapter!(ReadyList = Task { ready });
+//! link_adapter!(TimerList = Task { timer });
+//!
+//! let new_task = |id| Task { id, ready: Link::new(), timer: Link::new() };
+//! let a = pin!(new_task(0));
+//! let b = pin!(new_task(1));
+//! let c = pin!(new_task(2));
+//!
+//! // the tasks are pinned on the stack, and `Task` is not `Unpin`
+//! unsafe {
+//!     ReadyList::add(a.as_ref(), b.as_ref());
+//!     ReadyList::add(b.as_ref(), c.as_ref());
+//!     TimerList::add(c.as_ref(), a.as_ref());
+//! }
+//!
+//! let mut ready = vec![];
+//! ReadyList::for_each(&a, |task| ready.push(task.id));
+//! assert_eq!(ready, [0, 1, 2]);
+//!
+//! let mut timer = vec![];
+//! TimerList::for_each(&c, |task| timer.push(task.id));
+//! assert_eq!(timer, [2, 0]);
+//!
+//! // leaving one list does not affect the other
+//! ReadyList::remove(&a);
+//! assert_eq!(ReadyList::len(&b), 2);
+//! assert_eq!(TimerList::len(&a), 2);
+//! ```
+use core::{cell::Cell, marker::PhantomPinned, pin::Pin, ptr::NonNull};
+
+/// A pair of links to be embedded in a struct,
+/// one field for each list the struct can be on.
+///
+/// A link is `!Unpin`, so the struct containing it must be pinned
+/// to be added to a list. It is removed from its list when dropped.
+pub struct Link {
+    prev: Cell<Option<NonNull<Link>>>,
+    next: Cell<Option<NonNull<Link>>>,
+    _pin: PhantomPinned,
+}
+
+impl Link {
+    /// Creates a link which is not on any list.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            prev: Cell::new(None),
+            next: Cell::new(None),
+            _pin: PhantomPinned,
+        }
+    }
+
+    /// Returns `true` if the link is on a list with other links.
+    #[inline]
+    pub fn is_linked(&self) -> bool {
+        self.next.get().is_some()
+    }
+
+    /// A standalone link has no pointers, and is its own neighbor.
+    #[inline(always)]
+    unsafe fn next_of(this: NonNull<Link>) -> NonNull<Link> {
+        this.as_ref().next.get().unwrap_or(this)
+    }
+
+    #[inline(always)]
+    unsafe fn prev_of(this: NonNull<Link>) -> NonNull<Link> {
+        this.as_ref().prev.get().unwrap_or(this)
+    }
+
+    /// Removes `this` from its list, if any.
+    unsafe fn unlink(this: NonNull<Link>) {
+        let link = this.as_ref();
+        if let (Some(prev), Some(next)) = (link.prev.get(), link.next.get()) {
+            if prev == next {
+                // the remaining link becomes standalone
+                prev.as_ref().prev.set(None);
+                prev.as_ref().next.set(None);
+            } else {
+                prev.as_ref().next.set(Some(next));
+                next.as_ref().prev.set(Some(prev));
+            }
+            link.prev.set(None);
+            link.next.set(None);
+        }
+    }
+
+    /// Inserts the standalone link `other` after `this`.
+    unsafe fn insert_after(this: NonNull<Link>, other: NonNull<Link>) {
+        let next = Self::next_of(this);
+        other.as_ref().prev.set(Some(this));
+        other.as_ref().next.set(Some(next));
+        this.as_ref().next.set(Some(other));
+        next.as_ref().prev.set(Some(other));
+    }
+}
+
+impl Default for Link {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        unsafe { Self::unlink(NonNull::from(&*self)) }
+    }
+}
+
+/// Locates a [`Link`] field inside `Value`, defining one list
+/// over values of that type.
+///
+/// Implement it with [`link_adapter!`](crate::link_adapter).
+///
+/// # Safety
+/// `offset` must return the offset in bytes of a field
+/// of type `Link` within `Value`.
+pub unsafe trait Adapter {
+    /// The type of the values on the list.
+    type Value;
+
+    /// Returns the offset of the link field within `Value`.
+    fn offset() -> usize;
+
+    /// Removes `other` from its current position in this list
+    /// and inserts it after `this`.
+    ///
+    /// # Safety
+    /// `this` and `other` must not move until they are removed
+    /// from this list or dropped, since the other values on the list
+    /// point to them. Pinning guarantees it unless `Value` is `Unpin`.
+    unsafe fn add(this: Pin<&Self::Value>, other: Pin<&Self::Value>) {
+        let (this, other) = (link_ptr::<Self>(&this), link_ptr::<Self>(&other));
+        if this != other {
+            unsafe {
+                Link::unlink(other);
+                Link::insert_after(this, other);
+            }
+        }
+    }
+
+    /// Removes `other` from its current position in this list
+    /// and inserts it before `this`.
+    ///
+    /// # Safety
+    /// Same as [`Adapter::add`].
+    unsafe fn add_before(this: Pin<&Self::Value>, other: Pin<&Self::Value>) {
+        let (this, other) = (link_ptr::<Self>(&this), link_ptr::<Self>(&other));
+        if this != other {
+            unsafe {
+                Link::unlink(other);
+                Link::insert_after(Link::prev_of(this), other);
+            }
+        }
+    }
+
+    /// Removes `value` from this list, leaving it standalone.
+    fn remove(value: &Self::Value) {
+        unsafe { Link::unlink(link_ptr::<Self>(value)) }
+    }
+
+    /// Returns `true` if `value` is on this list with other values.
+    fn is_linked(value: &Self::Value) -> bool {
+        unsafe { link_ptr::<Self>(value).as_ref() }.is_linked()
+    }
+
+    /// Iterates over each value in this list starting from `value`,
+    /// and applies function `f` to it.
+    ///
+    /// If `f` removes values from this list,
+    /// the walk may end before all the remaining values are visited.
+    fn for_each<F>(value: &Self::Value, mut f: F)
+    where
+        F: FnMut(&Self::Value),
+    {
+        let start = link_ptr::<Self>(value);
+        let mut this = start;
+        loop {
+            f(unsafe { value_ptr::<Self>(this).as_ref() });
+            let link = unsafe { this.as_ref() };
+            match link.next.get() {
+                Some(next) if next != start && unsafe { start.as_ref() }.is_linked() => {
+                    this = next;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Returns the number of values in this list.
+    fn len(value: &Self::Value) -> usize {
+        let mut len = 0;
+        Self::for_each(value, |_| len += 1);
+        len
+    }
+}
+
+/// Returns a pointer to the link of `value`, keeping the
+/// provenance of the whole value to get back to it later.
+#[inline(always)]
+fn link_ptr<A: Adapter + ?Sized>(value: &A::Value) -> NonNull<Link> {
+    let value = NonNull::from(value).cast::<u8>();
+    unsafe { NonNull::new_unchecked(value.as_ptr().add(A::offset())).cast() }
+}
+
+#[inline(always)]
+unsafe fn value_ptr<A: Adapter + ?Sized>(link: NonNull<Link>) -> NonNull<A::Value> {
+    let link = link.cast::<u8>();
+    NonNull::new_unchecked(link.as_ptr().sub(A::offset())).cast()
+}
+
+/// Declares a unit struct implementing [`Adapter`] for a `Link`
+/// field of a struct.
+///
+/// ```
+/// use cdlist::{link_adapter, Link};
+///
+/// pub struct Task {
+///     ready: Link,
+/// }
+///
+/// link_adapter!(pub ReadyList = Task { ready });
+/// ```
+///
+/// The field must be a `Link`.
+/// ```compile_fail,E0308
+/// use cdlist::link_adapter;
+///
+/// struct Task {
+///     ready: u32,
+/// }
+///
+/// link_adapter!(ReadyList = Task { ready });
+/// ```
+#[macro_export]
+macro_rules! link_adapter {
+    ($(#[$attr:meta])* $vis:vis $name:ident = $value:ty { $field:ident }) => {
+        $(#[$attr])*
+        $vis struct $name;
+
+        unsafe impl $crate::Adapter for $name {
+            type Value = $value;
+
+            #[inline(always)]
+            fn offset() -> usize {
+                let _: fn(&$value) -> &$crate::Link = |value| &value.$field;
+                ::core::mem::offset_of!($value, $field)
+            }
+        }
+    };
+}
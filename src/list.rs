@@ -0,0 +1,445 @@
+//! An owning list, built on the same nodes as `LinkNode`.
+use crate::{
+    iter::{ExactIter, Iter, IterMut, RawCursors},
+    Inner, LinkNode, ListHead,
+};
+use alloc::{boxed::Box, collections::LinkedList};
+use core::{
+    fmt::{self, Debug},
+    iter::FusedIterator,
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+/// A doubly-linked list owning all of its nodes,
+/// with a `VecDeque`-like API.
+///
+/// Unlike a `LinkNode`, which only owns itself, a `List` owns
+/// every node it contains and frees them when dropped.
+/// The nodes are linked in a ring with a sentinel head, which
+/// holds no data, so the list can be empty and knows its length.
+///
+/// It is the deque to use instead of a ring anchored at a `LinkNode`:
+/// such a ring has no owner, so pushing an owned node into it would
+/// leak the node, and popping one back could not be checked to be sound.
+///
+/// # Example
+/// ```
+/// use cdlist::List;
+///
+/// let mut list = List::new();
+/// list.push_back(1);
+/// list.push_back(2);
+/// list.push_front(0);
+/// assert_eq!(list.len(), 3);
+/// assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+///
+/// assert_eq!(list.pop_back(), Some(2));
+/// assert_eq!(list.pop_front(), Some(0));
+/// assert_eq!(list.pop_front(), Some(1));
+/// assert_eq!(list.pop_front(), None);
+/// assert!(list.is_empty());
+/// ```
+pub struct List<T> {
+    sentinel: NonNull<ListHead<T>>,
+    len: usize,
+    marker: PhantomData<Box<Inner<T>>>,
+}
+
+impl<T> List<T> {
+    /// Creates an empty list.
+    #[must_use]
+    pub fn new() -> Self {
+        let sentinel = NonNull::from(Box::leak(Box::new(ListHead::new())));
+        unsafe {
+            ListHead::init_head(sentinel);
+            Self {
+                sentinel,
+                len: 0,
+                marker: PhantomData,
+            }
+        }
+    }
+
+    /// Returns the number of elements in the list, in O(1).
+    ///
+    /// The length is cached in the list, and updated by every
+    /// operation adding or removing elements.
+    #[must_use]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Counts the elements by walking the whole list, in O(n).
+    /// It always equals [`List::len`], and is meant to check it.
+    #[must_use]
+    pub fn count(&self) -> usize {
+        let mut count = 0;
+        unsafe { ListHead::walk(self.sentinel, ListHead::next_ptr, |_| count += 1) };
+        // the sentinel holds no element
+        count - 1
+    }
+
+    /// Returns `true` if the list has no elements.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends an element to the back of the list.
+    pub fn push_back(&mut self, data: T) {
+        self.push_back_node(LinkNode::new(data));
+    }
+
+    /// Removes `node` from its current list and appends it
+    /// to the back of the list, which takes ownership of it.
+    fn push_back_node(&mut self, mut node: LinkNode<T>) {
+        node.take();
+        let node = node.into_raw();
+        unsafe { ListHead::add_before(self.sentinel, node) };
+        self.len += 1;
+    }
+
+    /// Prepends an element to the front of the list.
+    pub fn push_front(&mut self, data: T) {
+        let node = LinkNode::new(data).into_raw();
+        unsafe { ListHead::add(self.sentinel, node) };
+        self.len += 1;
+    }
+
+    /// Removes the first element and returns it,
+    /// or `None` if the list is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let node = unsafe { LinkNode::from_raw(ListHead::next_ptr(self.sentinel)) };
+        Some(node.into_inner())
+    }
+
+    /// Removes the last element and returns it,
+    /// or `None` if the list is empty.
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let node = unsafe { LinkNode::from_raw(ListHead::prev_ptr(self.sentinel)) };
+        Some(node.into_inner())
+    }
+
+    /// Removes consecutive elements that `same` considers equal,
+    /// from front to back, dropping them. Each element is compared
+    /// with the last element kept, as `same(element, kept)`.
+    ///
+    /// ```
+    /// use cdlist::List;
+    ///
+    /// let mut list = List::new();
+    /// list.extend([1, 1, 2, 2, 2, 3, 1]);
+    /// list.dedup_by(|a, b| a == b);
+    /// assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 1]);
+    /// ```
+    pub fn dedup_by<F>(&mut self, mut same: F)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let mut kept = unsafe { ListHead::next_ptr(self.sentinel) };
+        if kept == self.sentinel {
+            return;
+        }
+        let mut this = unsafe { ListHead::next_ptr(kept) };
+        while this != self.sentinel {
+            let next = unsafe { ListHead::next_ptr(this) };
+            let duplicate = unsafe {
+                same(
+                    ListHead::data_ptr(this).as_ref(),
+                    ListHead::data_ptr(kept).as_ref(),
+                )
+            };
+            if duplicate {
+                self.len -= 1;
+                drop(unsafe { LinkNode::from_raw(this) });
+            } else {
+                kept = this;
+            }
+            this = next;
+        }
+    }
+
+    /// Keeps only the elements for which `keep` returns `true`,
+    /// visiting them from front to back, and drops the others.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut this = unsafe { ListHead::next_ptr(self.sentinel) };
+        while this != self.sentinel {
+            let next = unsafe { ListHead::next_ptr(this) };
+            if !keep(unsafe { ListHead::data_ptr(this).as_ref() }) {
+                self.len -= 1;
+                drop(unsafe { LinkNode::from_raw(this) });
+            }
+            this = next;
+        }
+    }
+
+    /// Removes all the elements from front to back, and returns
+    /// an iterator yielding their nodes, standalone and owned by the caller.
+    ///
+    /// The list is empty once the iterator is dropped,
+    /// even if it was not fully consumed: the remaining
+    /// elements are dropped with it.
+    ///
+    /// ```
+    /// use cdlist::List;
+    ///
+    /// let mut list = List::new();
+    /// list.extend(0..3);
+    /// let drained = list.drain().map(|node| node.into_inner());
+    /// assert_eq!(drained.collect::<Vec<_>>(), [0, 1, 2]);
+    /// assert!(list.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { list: self }
+    }
+
+    /// Removes the first node and returns it standalone,
+    /// or `None` if the list is empty.
+    fn pop_front_node(&mut self) -> Option<LinkNode<T>> {
+        if self.is_empty() {
+            return None;
+        }
+        self.len -= 1;
+        let mut node = unsafe { LinkNode::from_raw(ListHead::next_ptr(self.sentinel)) };
+        node.take();
+        Some(node)
+    }
+
+    /// Returns an iterator which removes the elements for which
+    /// `pred` returns `true`, visiting them from front to back,
+    /// and yields their nodes, standalone and owned by the caller.
+    ///
+    /// Each element is visited only as the iterator advances,
+    /// and elements left after it is dropped stay in the list.
+    ///
+    /// ```
+    /// use cdlist::List;
+    ///
+    /// let mut list = List::new();
+    /// list.extend(0..6);
+    /// let odd = list.extract_if(|i| *i % 2 == 1).map(|node| *node);
+    /// assert_eq!(odd.collect::<Vec<_>>(), [1, 3, 5]);
+    /// assert_eq!(list.iter().copied().collect::<Vec<_>>(), [0, 2, 4]);
+    /// ```
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf {
+            next: unsafe { ListHead::next_ptr(self.sentinel) },
+            list: self,
+            pred,
+        }
+    }
+
+    /// Returns an iterator over immutable references
+    /// to the elements, from front to back.
+    #[inline]
+    pub fn iter(&self) -> ExactIter<'_, T> {
+        let raw = unsafe { RawCursors::excluding(self.sentinel) };
+        ExactIter::new(unsafe { Iter::from_raw(raw) }, self.len)
+    }
+
+    /// Returns an iterator over mutable references
+    /// to the elements, from front to back.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        let raw = unsafe { RawCursors::excluding(self.sentinel) };
+        unsafe { IterMut::from_raw(raw) }
+    }
+}
+
+/// An iterator removing all the elements of a [`List`],
+/// and yielding their nodes.
+///
+/// Created by [`List::drain`].
+pub struct Drain<'a, T> {
+    list: &'a mut List<T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = LinkNode<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<LinkNode<T>> {
+        self.list.pop_front_node()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        while self.list.pop_front().is_some() {}
+    }
+}
+
+/// An iterator removing the elements of a [`List`] that match
+/// a predicate, and yielding their nodes.
+///
+/// Created by [`List::extract_if`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ExtractIf<'a, T, F> {
+    list: &'a mut List<T>,
+    next: NonNull<ListHead<T>>,
+    pred: F,
+}
+
+impl<T, F> Iterator for ExtractIf<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    type Item = LinkNode<T>;
+
+    fn next(&mut self) -> Option<LinkNode<T>> {
+        while self.next != self.list.sentinel {
+            let this = self.next;
+            self.next = unsafe { ListHead::next_ptr(this) };
+            if (self.pred)(unsafe { ListHead::data_ptr(this).as_mut() }) {
+                self.list.len -= 1;
+                let mut node = unsafe { LinkNode::from_raw(this) };
+                node.take();
+                return Some(node);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.list.len))
+    }
+}
+
+impl<T, F> FusedIterator for ExtractIf<'_, T, F> where F: FnMut(&mut T) -> bool {}
+
+impl<T> Default for List<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Drop for List<T> {
+    fn drop(&mut self) {
+        while self.pop_front().is_some() {}
+        drop(unsafe { Box::from_raw(self.sentinel.as_ptr()) });
+    }
+}
+
+impl<T> Extend<T> for List<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|data| self.push_back(data));
+    }
+}
+
+/// Builds a list from the elements of an iterator, in order.
+///
+/// ```
+/// use cdlist::List;
+///
+/// let list: List<i32> = (0..3).collect();
+/// assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+/// ```
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+/// Moves the elements of a `LinkedList` into a new list, in order.
+///
+/// ```
+/// use cdlist::List;
+/// use std::collections::LinkedList;
+///
+/// let list = List::from(LinkedList::from([1, 2, 3]));
+/// assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// assert_eq!(LinkedList::from(list), LinkedList::from([1, 2, 3]));
+/// ```
+impl<T> From<LinkedList<T>> for List<T> {
+    fn from(list: LinkedList<T>) -> Self {
+        list.into_iter().collect()
+    }
+}
+
+/// Moves the elements of a list into a new `LinkedList`, in order.
+impl<T> From<List<T>> for LinkedList<T> {
+    fn from(mut list: List<T>) -> Self {
+        let mut linked = LinkedList::new();
+        while let Some(data) = list.pop_front() {
+            linked.push_back(data);
+        }
+        linked
+    }
+}
+
+/// Appends already constructed nodes to the back of the list,
+/// in order. Each node is first removed from the list it was in.
+///
+/// The list takes ownership of the nodes, and frees them
+/// when they are popped or when the list is dropped, as if their
+/// data had been pushed with [`List::push_back`].
+///
+/// ```
+/// use cdlist::{LinkNode, List};
+///
+/// let mut list = List::<i32>::new();
+/// list.extend((0..3).map(LinkNode::new));
+/// assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+/// ```
+impl<T> Extend<LinkNode<T>> for List<T> {
+    fn extend<I: IntoIterator<Item = LinkNode<T>>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|node| self.push_back_node(node));
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = ExactIter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut List<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
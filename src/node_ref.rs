@@ -0,0 +1,81 @@
+//! Lightweight handles to nodes visited during a traversal.
+use crate::ListHead;
+use core::{marker::PhantomData, ptr::NonNull};
+
+/// An opaque identifier of a node, based on its address.
+///
+/// Two identifiers compare equal exactly when they belong to
+/// the same node, as long as both nodes are alive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(usize);
+
+impl NodeId {
+    #[inline]
+    pub(crate) fn new<T>(list: NonNull<ListHead<T>>) -> Self {
+        Self(list.as_ptr() as usize)
+    }
+}
+
+/// A read-only handle to a node visited during a traversal.
+///
+/// It gives access to the data of the node and some information
+/// about its position, but does not allow modifying the list.
+///
+/// Created by [`LinkNode::for_each_node`](crate::LinkNode::for_each_node).
+pub struct NodeRef<'a, T> {
+    list: NonNull<ListHead<T>>,
+    start: NonNull<ListHead<T>>,
+    index: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> NodeRef<'a, T> {
+    /// `list` must be a node of a list borrowed for `'a`.
+    #[inline]
+    pub(crate) unsafe fn new(
+        list: NonNull<ListHead<T>>,
+        start: NonNull<ListHead<T>>,
+        index: usize,
+    ) -> Self {
+        Self {
+            list,
+            start,
+            index,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an immutable reference to the data of the node.
+    #[inline]
+    pub fn data(&self) -> &'a T {
+        unsafe { ListHead::data_ptr(self.list).as_ref() }
+    }
+
+    /// Returns `true` if the node is the one the traversal started from.
+    #[inline]
+    pub fn is_start(&self) -> bool {
+        self.list == self.start
+    }
+
+    /// Returns the position of the node relative to
+    /// the node the traversal started from.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the identifier of the node.
+    #[inline]
+    pub fn id(&self) -> NodeId {
+        NodeId::new(self.list)
+    }
+}
+
+impl<T> Clone for NodeRef<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeRef<'_, T> {}
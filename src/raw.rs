@@ -0,0 +1,83 @@
+//! An unsafe API for walking a list through raw node handles.
+use crate::{iter::RawCursors, ListHead};
+use core::{fmt, ptr::NonNull};
+
+/// An opaque raw handle to a node, which can be stored
+/// outside of any borrow and used to access the node later.
+///
+/// Using a handle is unsafe: the node it refers to must
+/// still be alive, which the handle cannot guarantee.
+///
+/// Obtained from [`LinkNode::as_raw`](crate::LinkNode::as_raw)
+/// or [`LinkNode::raw_iter`](crate::LinkNode::raw_iter).
+pub struct RawNode<T>(NonNull<ListHead<T>>);
+
+impl<T> RawNode<T> {
+    #[inline]
+    pub(crate) fn new(list: NonNull<ListHead<T>>) -> Self {
+        Self(list)
+    }
+
+    #[inline]
+    pub(crate) fn as_ptr(self) -> NonNull<ListHead<T>> {
+        self.0
+    }
+}
+
+impl<T> Clone for RawNode<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for RawNode<T> {}
+
+impl<T> PartialEq for RawNode<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for RawNode<T> {}
+
+impl<T> fmt::Debug for RawNode<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawNode").field(&self.0).finish()
+    }
+}
+
+/// An iterator over raw handles to each node in a list,
+/// starting from a given node.
+///
+/// Created by [`LinkNode::raw_iter`](crate::LinkNode::raw_iter).
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RawIter<T> {
+    raw: RawCursors<T>,
+}
+
+impl<T> RawIter<T> {
+    #[inline]
+    pub(crate) unsafe fn new(start: NonNull<ListHead<T>>) -> Self {
+        Self {
+            raw: RawCursors::new(start),
+        }
+    }
+}
+
+impl<T> Iterator for RawIter<T> {
+    type Item = RawNode<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.raw.next().map(RawNode)
+    }
+}
+
+impl<T> DoubleEndedIterator for RawIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.raw.next_back().map(RawNode)
+    }
+}
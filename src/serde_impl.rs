@@ -0,0 +1,55 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! Lists are serialized as sequences of their data. Since a `LinkNode`
+//! only owns itself, deserialization builds an owning [`List`] instead.
+use crate::{LinkNode, List};
+use core::{fmt, marker::PhantomData};
+use serde::{
+    de::{SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// Serializes the list walked forward from the node as a sequence.
+///
+/// The length is walked first, for the formats that write it
+/// before the elements.
+impl<T: Serialize> Serialize for LinkNode<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        self.try_for_each_err(|data| seq.serialize_element(data))?;
+        seq.end()
+    }
+}
+
+/// Serializes the list from front to back as a sequence.
+impl<T: Serialize> Serialize for List<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+/// Deserializes a sequence into a list, in order.
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for List<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(ListVisitor(PhantomData))
+    }
+}
+
+struct ListVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for ListVisitor<T> {
+    type Value = List<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = List::new();
+        while let Some(data) = seq.next_element()? {
+            list.push_back(data);
+        }
+        Ok(list)
+    }
+}
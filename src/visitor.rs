@@ -0,0 +1,157 @@
+//! Reusable traversal logic, driven by
+//! [`LinkNode::accept`](crate::LinkNode::accept) and
+//! [`LinkNode::accept_mut`](crate::LinkNode::accept_mut).
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+/// A visitor applied to the items of a list, in order.
+///
+/// The driver calls `begin` once, then `visit` for each item
+/// until the visitor breaks or the list is exhausted,
+/// and finally `finish` once, even after an early break.
+pub trait RingVisitor<T> {
+    /// Called before the first item.
+    ///
+    /// `len_hint` is the length of the list when known without
+    /// walking it, which is never the case for a `LinkNode`.
+    fn begin(&mut self, len_hint: Option<usize>) {
+        let _ = len_hint;
+    }
+
+    /// Called with each item and its index from the start.
+    /// Returning `ControlFlow::Break` stops the walk.
+    fn visit(&mut self, index: usize, data: &T) -> ControlFlow<()>;
+
+    /// Called after the last visited item.
+    fn finish(&mut self) {}
+}
+
+/// Like [`RingVisitor`], but with mutable access to the items.
+pub trait RingVisitorMut<T> {
+    /// Called before the first item.
+    ///
+    /// `len_hint` is the length of the list when known without
+    /// walking it, which is never the case for a `LinkNode`.
+    fn begin(&mut self, len_hint: Option<usize>) {
+        let _ = len_hint;
+    }
+
+    /// Called with each item and its index from the start.
+    /// Returning `ControlFlow::Break` stops the walk.
+    fn visit(&mut self, index: usize, data: &mut T) -> ControlFlow<()>;
+
+    /// Called after the last visited item.
+    fn finish(&mut self) {}
+}
+
+/// A visitor counting the items it visits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counter {
+    count: usize,
+}
+
+impl Counter {
+    /// Creates a counter starting at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of items visited so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl<T> RingVisitor<T> for Counter {
+    fn visit(&mut self, _: usize, _: &T) -> ControlFlow<()> {
+        self.count += 1;
+        ControlFlow::Continue(())
+    }
+}
+
+impl<T> RingVisitorMut<T> for Counter {
+    fn visit(&mut self, _: usize, _: &mut T) -> ControlFlow<()> {
+        self.count += 1;
+        ControlFlow::Continue(())
+    }
+}
+
+/// A visitor cloning the items it visits into a `Vec`,
+/// optionally stopping after a given number of items.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Collector<T> {
+    items: Vec<T>,
+    limit: Option<usize>,
+}
+
+impl<T> Collector<T> {
+    /// Creates a collector taking every item.
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            limit: None,
+        }
+    }
+
+    /// Creates a collector breaking after `limit` items.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            items: Vec::new(),
+            limit: Some(limit),
+        }
+    }
+
+    /// Returns the items collected so far.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Consumes the collector, returning the collected items.
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    fn push(&mut self, data: &T) -> ControlFlow<()>
+    where
+        T: Clone,
+    {
+        if self.limit == Some(self.items.len()) {
+            return ControlFlow::Break(());
+        }
+        self.items.push(data.clone());
+        if self.limit == Some(self.items.len()) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+impl<T> Default for Collector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> RingVisitor<T> for Collector<T> {
+    fn begin(&mut self, len_hint: Option<usize>) {
+        if let Some(len) = len_hint {
+            self.items
+                .reserve(self.limit.map_or(len, |limit| limit.min(len)));
+        }
+    }
+
+    fn visit(&mut self, _: usize, data: &T) -> ControlFlow<()> {
+        self.push(data)
+    }
+}
+
+impl<T: Clone> RingVisitorMut<T> for Collector<T> {
+    fn begin(&mut self, len_hint: Option<usize>) {
+        RingVisitor::begin(self, len_hint);
+    }
+
+    fn visit(&mut self, _: usize, data: &mut T) -> ControlFlow<()> {
+        self.push(data)
+    }
+}
@@ -0,0 +1,61 @@
+#![cfg(feature = "allocator_api")]
+#![feature(allocator_api)]
+
+use cdlist::LinkNode;
+use std::{
+    alloc::{AllocError, Allocator, Global, Layout},
+    cell::Cell,
+    ptr::NonNull,
+    rc::Rc,
+};
+
+/// Counts the live allocations made through it.
+struct Counting(Rc<Cell<usize>>);
+
+unsafe impl Allocator for Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.set(self.0.get() - 1);
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn new_in_global() {
+    let mut node0 = LinkNode::new_in(0, Global);
+    let mut node1 = LinkNode::new_in(1, Global);
+    let mut node2 = LinkNode::new(2);
+    node0.add(&mut node1);
+    node1.add(&mut node2);
+    assert_eq!(node0.to_vec(), [0, 1, 2]);
+    node0.for_each_mut(|i| *i += 1);
+    assert_eq!(node2.to_vec(), [3, 1, 2]);
+    drop(node1);
+    assert_eq!(node0.to_vec(), [1, 3]);
+    assert_eq!(node0.into_inner(), 1);
+    assert!(node2.is_standalone());
+}
+
+#[test]
+fn new_in_custom() {
+    let live = Rc::new(Cell::new(0));
+    let mut node0 = LinkNode::new_in(String::from("a"), Counting(live.clone()));
+    let mut node1 = LinkNode::new_in(String::from("b"), Counting(live.clone()));
+    assert_eq!(live.get(), 2);
+    node0.add(&mut node1);
+    assert_eq!(
+        unsafe { node1.iter() }
+            .map(String::as_str)
+            .collect::<String>(),
+        "ba"
+    );
+
+    assert_eq!(node1.into_inner(), "b");
+    assert_eq!(live.get(), 1);
+    drop(node0);
+    assert_eq!(live.get(), 0);
+}
@@ -1,4 +1,17 @@
-use cdlist::LinkNode;
+use cdlist::{
+    link_adapter, Adapter, Collector, Counter, Link, LinkNode, List, NodeRef, RingVisitor,
+    RingVisitorMut,
+};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, VecDeque},
+    hash::{DefaultHasher, Hash, Hasher},
+    num::NonZeroUsize,
+    ops::ControlFlow,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    rc::Rc,
+};
 
 #[test]
 fn deref_mut() {
@@ -22,6 +35,47 @@ fn iter() {
     assert_eq!(collect_rev(&nodes[9]), (0..10).rev().collect::<Vec<_>>());
 }
 
+#[test]
+fn iterator_single() {
+    let node0 = LinkNode::new(0);
+    assert_eq!(unsafe { node0.iter() }.collect::<Vec<_>>(), vec![&0]);
+}
+
+#[test]
+fn iterator_collect() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    assert_eq!(
+        unsafe { nodes[0].iter() }.copied().collect::<Vec<_>>(),
+        (0..10).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        unsafe { nodes[3].iter() }
+            .filter(|&&i| i % 2 == 0)
+            .map(|&i| i * 10)
+            .take(3)
+            .collect::<Vec<_>>(),
+        vec![40, 60, 80]
+    );
+}
+
+#[test]
+fn iterator() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    for node in &nodes {
+        assert_eq!(
+            unsafe { node.iter() }.copied().collect::<Vec<_>>(),
+            collect(node)
+        );
+    }
+    let pairs = unsafe { nodes[0].iter() }
+        .zip(unsafe { nodes[5].iter() })
+        .collect::<Vec<_>>();
+    assert_eq!(pairs[0], (&0, &5));
+    assert_eq!(pairs[9], (&9, &4));
+}
+
 #[test]
 fn iter_mut() {
     let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
@@ -43,6 +97,177 @@ fn iter_mut() {
     );
 }
 
+#[test]
+fn iterator_mut() {
+    let mut node0 = LinkNode::new(0);
+    unsafe { node0.iter_mut() }.for_each(|i| *i += 1);
+    assert_eq!(collect(&node0), vec![1]);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    for (j, i) in unsafe { nodes[5].iter_mut() }.enumerate() {
+        *i += j;
+    }
+    assert_eq!(collect(&nodes[0]), vec![5, 7, 9, 11, 13, 5, 7, 9, 11, 13]);
+    for i in unsafe { nodes[0].iter_mut() } {
+        if *i > 10 {
+            break;
+        }
+        *i = 0;
+    }
+    assert_eq!(collect(&nodes[0]), vec![0, 0, 0, 11, 13, 5, 7, 9, 11, 13]);
+}
+
+#[test]
+fn iterator_double_ended() {
+    let node0 = LinkNode::new(0);
+    assert_eq!(unsafe { node0.iter() }.rev().collect::<Vec<_>>(), vec![&0]);
+
+    let mut nodes = (0..2).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 2);
+    let mut iter = unsafe { nodes[0].iter() };
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.next_back(), Some(&1));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    assert_eq!(
+        collect_rev(&nodes[9]),
+        unsafe { nodes[0].iter() }
+            .rev()
+            .copied()
+            .collect::<Vec<_>>()
+    );
+    // interleave front and back against a reference model
+    for pattern in 0..(1 << 10) {
+        let mut model = (3..10).chain(0..3).collect::<VecDeque<_>>();
+        let mut iter = unsafe { nodes[3].iter() };
+        for step in 0..12 {
+            if pattern & (1 << (step % 10)) == 0 {
+                assert_eq!(iter.next().copied(), model.pop_front());
+            } else {
+                assert_eq!(iter.next_back().copied(), model.pop_back());
+            }
+        }
+    }
+}
+
+#[test]
+fn iterator_interleave() {
+    let node0 = LinkNode::new(0);
+    let mut iter = unsafe { node0.iter() };
+    assert_eq!(iter.next_back(), Some(&0));
+    assert_eq!(iter.next(), None);
+
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let mut iter = unsafe { nodes[2].iter() };
+    assert_eq!(iter.next(), Some(&2));
+    assert_eq!(iter.next_back(), Some(&1));
+    assert_eq!(iter.next_back(), Some(&0));
+    assert_eq!(iter.next(), Some(&3));
+    assert_eq!(iter.next_back(), Some(&4));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+}
+
+#[test]
+fn iterator_exact() {
+    let node0 = LinkNode::new(0);
+    let mut iter = unsafe { node0.iter_exact() };
+    assert_eq!(iter.len(), 1);
+    assert_eq!(iter.next_back(), Some(&0));
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    let mut iter = unsafe { nodes[4].iter_exact() };
+    assert_eq!(iter.size_hint(), (10, Some(10)));
+    for step in 0..10 {
+        assert_eq!(iter.len(), 10 - step);
+        let item = if step % 3 == 0 {
+            iter.next_back()
+        } else {
+            iter.next()
+        };
+        assert!(item.is_some());
+    }
+    assert_eq!(iter.len(), 0);
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.len(), 0);
+
+    let vec = unsafe { nodes[0].iter_exact() }.collect::<Vec<_>>();
+    assert_eq!(vec.capacity(), 10);
+}
+
+#[test]
+fn iterator_mut_double_ended() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let mut iter = unsafe { nodes[0].iter_mut() };
+    while let (Some(front), Some(back)) = (iter.next(), iter.next_back()) {
+        std::mem::swap(front, back);
+    }
+    assert_eq!(collect(&nodes[0]), vec![4, 3, 2, 1, 0]);
+    for (j, i) in unsafe { nodes[0].iter_mut() }.rev().enumerate() {
+        *i += j * 10;
+    }
+    assert_eq!(collect(&nodes[0]), vec![44, 33, 22, 11, 0]);
+}
+
+#[test]
+fn iterator_mut_in_place() {
+    let mut nodes = (0..10).map(|i| LinkNode::new(vec![i])).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    let refs = unsafe { nodes[0].iter_mut() }.collect::<Vec<_>>();
+    for v in refs {
+        v.push(v[0] * 2);
+    }
+    for (i, v) in unsafe { nodes[0].iter() }.enumerate() {
+        assert_eq!(v, &vec![i, i * 2]);
+    }
+}
+
+#[test]
+fn iterator_cycle() {
+    let node0 = LinkNode::new(0);
+    assert_eq!(
+        unsafe { node0.cycle_iter() }.take(3).collect::<Vec<_>>(),
+        vec![&0; 3]
+    );
+    assert_eq!(
+        unsafe { node0.cycle_iter() }.size_hint(),
+        (usize::MAX, None)
+    );
+
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let vec = unsafe { nodes[1].cycle_iter() }
+        .take(10)
+        .copied()
+        .collect::<Vec<_>>();
+    assert_eq!(vec, vec![1, 2, 0, 1, 2, 0, 1, 2, 0, 1]);
+}
+
+#[test]
+fn into_iterator() {
+    // only a `List` owns all its nodes, so only it can lend them to a `for` loop
+    let mut list = (0..10).collect::<List<_>>();
+    for i in &mut list {
+        *i *= 2;
+    }
+    let mut vec = vec![];
+    for i in &list {
+        vec.push(*i);
+    }
+    assert_eq!(vec, (0..10).map(|i| i * 2).collect::<Vec<_>>());
+}
+
 #[test]
 fn pop_self() {
     let mut node0 = LinkNode::new(0);
@@ -93,6 +318,2303 @@ fn add() {
     assert_eq!(collect_rev(&nodes[9]), vec![9, 8, 6, 5]);
 }
 
+#[test]
+fn len() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    assert_eq!(nodes[0].len(), 1);
+    connect_all(&mut nodes, 0, 10);
+    for node in &nodes {
+        assert_eq!(node.len(), 10);
+    }
+    nodes[3].take();
+    assert_eq!(nodes[3].len(), 1);
+    assert!(nodes[3].is_singleton());
+    assert!(!nodes[0].is_singleton());
+    assert_eq!(nodes[0].len(), 9);
+}
+
+#[test]
+fn is_standalone() {
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    assert!(nodes.iter().all(LinkNode::is_standalone));
+    connect_all(&mut nodes, 0, 3);
+    assert!(!nodes.iter().any(LinkNode::is_standalone));
+    nodes[1].take();
+    assert!(nodes[1].is_standalone());
+    assert!(!nodes[0].is_standalone());
+    nodes[2].take();
+    assert!(nodes[0].is_standalone());
+}
+
+#[test]
+fn for_each_others() {
+    for len in [1, 2, 5] {
+        let mut nodes = (0..len).map(LinkNode::new).collect::<Vec<_>>();
+        connect_all(&mut nodes, 0, len);
+        let mut vec = vec![];
+        nodes[0].for_each_others(|&i| vec.push(i));
+        assert_eq!(vec, (1..len).collect::<Vec<_>>());
+        vec.clear();
+        nodes[0].for_each_others_rev(|&i| vec.push(i));
+        assert_eq!(vec, (1..len).rev().collect::<Vec<_>>());
+        nodes[0].for_each_others_mut(|i| *i += 10);
+        assert_eq!(collect(&nodes[0])[0], 0);
+        nodes[0].for_each_others_mut_rev(|i| *i += 10);
+        let expected = (0..len).map(|i| if i == 0 { 0 } else { i + 20 });
+        assert_eq!(collect(&nodes[0]), expected.collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn add_before() {
+    let mut a = LinkNode::new(0);
+    let mut b = LinkNode::new(1);
+    a.add_before(&mut b);
+    assert_eq!(collect(&b), vec![1, 0]);
+    assert_eq!(collect_rev(&a), vec![0, 1]);
+
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let (n0, n1) = nodes.split_at_mut(3);
+    n0[1].add_before(&mut n1[1]);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 1, 2, 3]);
+    assert_eq!(collect_rev(&nodes[3]), vec![3, 2, 1, 4, 0]);
+}
+
+#[test]
+fn add_before_to() {
+    // building a list in natural order while holding the head
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    let (head, rest) = nodes.split_first_mut().unwrap();
+    for node in rest.iter_mut() {
+        node.add_before_to(head);
+    }
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2, 3, 4]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 4, 3, 2, 1]);
+
+    // moving a node from the middle of another ring
+    let mut others = (10..13).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut others, 0, 3);
+    others[1].add_before_to(&mut nodes[2]);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 11, 2, 3, 4]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 4, 3, 2, 11, 1]);
+    assert_eq!(collect(&others[0]), vec![10, 12]);
+    assert_eq!(collect_rev(&others[0]), vec![10, 12]);
+}
+
+#[test]
+fn for_each_indexed() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    let mut pairs = vec![];
+    nodes[3].for_each_indexed(|i, &v| pairs.push((i, v)));
+    assert_eq!(
+        pairs,
+        (0..10).map(|i| (i, (i + 3) % 10)).collect::<Vec<_>>()
+    );
+    pairs.clear();
+    nodes[3].for_each_indexed(|i, &v| pairs.push((i, v)));
+    assert_eq!(pairs[0], (0, 3));
+    pairs.clear();
+    nodes[3].for_each_indexed_rev(|i, &v| pairs.push((i, v)));
+    assert_eq!(
+        pairs,
+        (0..10).map(|i| (i, (13 - i) % 10)).collect::<Vec<_>>()
+    );
+
+    nodes[0].for_each_indexed_mut(|i, v| *v += i * 10);
+    assert_eq!(
+        collect(&nodes[0]),
+        (0..10).map(|i| i * 11).collect::<Vec<_>>()
+    );
+    nodes[0].for_each_indexed_mut_rev(|i, v| *v -= i);
+    assert_eq!(
+        collect(&nodes[0]),
+        vec![0, 2, 14, 26, 38, 50, 62, 74, 86, 98]
+    );
+}
+
+#[test]
+fn peek() {
+    let node0 = LinkNode::new(0);
+    assert_eq!(node0.peek_next(|&i| i), None);
+    assert_eq!(node0.peek_prev(|&i| i), None);
+
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    assert_eq!(nodes[2].peek_next(|&i| i), Some(3));
+    assert_eq!(nodes[2].peek_prev(|&i| i), Some(1));
+    assert_eq!(nodes[4].peek_next(|&i| i), Some(0));
+    assert_eq!(nodes[0].peek_prev(|&i| i), Some(4));
+}
+
+#[test]
+fn for_each_pair() {
+    let node0 = LinkNode::new(0);
+    let mut pairs = vec![];
+    node0.for_each_pair(|&a, &b| pairs.push((a, b)));
+    assert_eq!(pairs, vec![(0, 0)]);
+    pairs.clear();
+    node0.for_each_pair_open(|&a, &b| pairs.push((a, b)));
+    assert!(pairs.is_empty());
+
+    let mut nodes = (0..4).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 4);
+    nodes[1].for_each_pair(|&a, &b| pairs.push((a, b)));
+    assert_eq!(pairs, vec![(1, 2), (2, 3), (3, 0), (0, 1)]);
+    pairs.clear();
+    nodes[1].for_each_pair_open(|&a, &b| pairs.push((a, b)));
+    assert_eq!(pairs, vec![(1, 2), (2, 3), (3, 0)]);
+}
+
+#[test]
+fn split_off() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    let (n0, n1) = nodes.split_at_mut(5);
+    n1[0].split_off(&mut n0[0]);
+    assert_eq!(collect(&nodes[0]), (0..5).collect::<Vec<_>>());
+    assert_eq!(collect_rev(&nodes[4]), (0..5).rev().collect::<Vec<_>>());
+    assert_eq!(collect(&nodes[5]), (5..10).collect::<Vec<_>>());
+    assert_eq!(collect_rev(&nodes[9]), (5..10).rev().collect::<Vec<_>>());
+
+    // split off the last node
+    let (n0, n1) = nodes.split_at_mut(4);
+    n1[0].split_off(&mut n0[0]);
+    assert_eq!(collect(&nodes[0]), (0..4).collect::<Vec<_>>());
+    assert_eq!(collect(&nodes[4]), vec![4]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "same list")]
+fn split_off_other_list() {
+    let mut n0 = LinkNode::new(0);
+    let mut n1 = LinkNode::new(1);
+    n0.split_off(&mut n1);
+}
+
+#[test]
+fn split_after() {
+    // split off a single node
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 6);
+    let (n0, n1) = nodes.split_at_mut(3);
+    n0[2].split_after(&mut n1[0]);
+    assert_eq!(collect(&nodes[3]), vec![3]);
+    assert!(nodes[3].is_standalone());
+    assert_eq!(collect(&nodes[2]), vec![2, 4, 5, 0, 1]);
+    assert_eq!(collect_rev(&nodes[2]), vec![2, 1, 0, 5, 4]);
+
+    // split off everything except `self`, across the wrap
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 6);
+    let (n0, n1) = nodes.split_at_mut(4);
+    n1[0].split_after(&mut n0[3]);
+    assert!(nodes[4].is_standalone());
+    assert_eq!(collect(&nodes[5]), vec![5, 0, 1, 2, 3]);
+    assert_eq!(collect_rev(&nodes[5]), vec![5, 3, 2, 1, 0]);
+
+    // split in the middle
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 6);
+    let (n0, n1) = nodes.split_at_mut(1);
+    n0[0].split_after(&mut n1[2]);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 5]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 5, 4]);
+    assert_eq!(collect(&nodes[1]), vec![1, 2, 3]);
+    assert_eq!(collect_rev(&nodes[1]), vec![1, 3, 2]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "same list")]
+fn split_after_other_list() {
+    let mut n0 = LinkNode::new(0);
+    let mut n1 = LinkNode::new(1);
+    n0.split_after(&mut n1);
+}
+
+#[test]
+fn for_each_windows() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let mut windows = vec![];
+    nodes[0].for_each_windows(|&[a, b, c]| windows.push([*a, *b, *c]));
+    assert_eq!(
+        windows,
+        vec![[0, 1, 2], [1, 2, 3], [2, 3, 4], [3, 4, 0], [4, 0, 1]]
+    );
+    let mut singles = vec![];
+    nodes[2].for_each_windows(|&[a]| singles.push(*a));
+    assert_eq!(singles, collect(&nodes[2]));
+
+    // shorter than the window
+    let mut nodes = (0..2).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 2);
+    windows.clear();
+    nodes[0].for_each_windows(|&[a, b, c]| windows.push([*a, *b, *c]));
+    assert_eq!(windows, vec![[0, 1, 0], [1, 0, 1]]);
+}
+
+#[test]
+fn append() {
+    let mut nodes = (0..8).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    connect_all(&mut nodes, 5, 8);
+    let (n0, n1) = nodes.split_at_mut(5);
+    n0[2].append(&mut n1[0]);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2, 5, 6, 7, 3, 4]);
+    assert_eq!(collect_rev(&nodes[4]), vec![4, 3, 7, 6, 5, 2, 1, 0]);
+
+    // standalone on either side
+    let mut n0 = LinkNode::new(0);
+    let mut n1 = LinkNode::new(1);
+    n0.append(&mut n1);
+    assert_eq!(collect(&n0), vec![0, 1]);
+    assert_eq!(collect_rev(&n0), vec![0, 1]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "different lists")]
+fn append_same_list() {
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let (n0, n1) = nodes.split_at_mut(1);
+    n0[0].append(&mut n1[0]);
+}
+
+#[test]
+fn splice_before() {
+    for len in [1, 2, 5] {
+        let mut nodes = (0..4 + len).map(LinkNode::new).collect::<Vec<_>>();
+        connect_all(&mut nodes, 0, 4);
+        connect_all(&mut nodes, 4, 4 + len);
+        let (n0, n1) = nodes.split_at_mut(4);
+        n0[2].splice_before(&mut n1[len - 1]);
+        let inserted = (4..4 + len).cycle().skip(len - 1).take(len);
+        let expected = [3, 0, 1]
+            .into_iter()
+            .chain(inserted)
+            .chain([2])
+            .collect::<Vec<_>>();
+        assert_eq!(collect(&nodes[3]), expected);
+        let mut reversed = expected.clone();
+        reversed[1..].reverse();
+        assert_eq!(collect_rev(&nodes[3]), reversed);
+    }
+
+    // before the first node, standalone on either side
+    let mut n0 = LinkNode::new(0);
+    let mut n1 = LinkNode::new(1);
+    n0.splice_before(&mut n1);
+    assert_eq!(collect(&n0), vec![0, 1]);
+    assert_eq!(collect_rev(&n0), vec![0, 1]);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "different lists")]
+fn splice_before_same_list() {
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let (n0, n1) = nodes.split_at_mut(1);
+    n0[0].splice_before(&mut n1[1]);
+}
+
+#[test]
+fn splice_after() {
+    // different lists
+    let mut nodes = (0..8).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    connect_all(&mut nodes, 5, 8);
+    let (n0, n1) = nodes.split_at_mut(5);
+    n0[2].splice_after(&mut n1[1]);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2, 6, 7, 5, 3, 4]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 4, 3, 5, 7, 6, 2, 1]);
+
+    // a standalone node is just added
+    let mut node = LinkNode::new(9);
+    nodes[4].splice_after(&mut node);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2, 6, 7, 5, 3, 4, 9]);
+
+    // the same list is rotated
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 6);
+    let (n0, n1) = nodes.split_at_mut(3);
+    n0[0].splice_after(&mut n1[1]);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 5, 1, 2, 3]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 3, 2, 1, 5, 4]);
+    let (n0, n1) = nodes.split_at_mut(3);
+    n1[1].splice_after(&mut n0[1]);
+    assert_eq!(collect(&nodes[0]), vec![0, 5, 4, 1, 2, 3]);
+
+    // already right after, and right before
+    let (n0, n1) = nodes.split_at_mut(5);
+    n0[0].splice_after(&mut n1[0]);
+    assert_eq!(collect(&nodes[0]), vec![0, 5, 4, 1, 2, 3]);
+    let (n0, n1) = nodes.split_at_mut(4);
+    n0[0].splice_after(&mut n1[0]);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 1, 2, 3, 5]);
+    assert_eq!(collect_rev(&nodes[5]), vec![5, 3, 2, 1, 4, 0]);
+}
+
+#[test]
+fn for_each_take() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    for (k, expected) in [(0, 0), (3, 3), (10, 10), (100, 10)] {
+        let mut vec = vec![];
+        nodes[5].for_each_take(k, |&i| vec.push(i));
+        assert_eq!(vec, collect(&nodes[5])[..expected]);
+        vec.clear();
+        nodes[5].for_each_take_rev(k, |&i| vec.push(i));
+        assert_eq!(vec, collect_rev(&nodes[5])[..expected]);
+    }
+    nodes[0].for_each_take_mut(3, |i| *i += 10);
+    nodes[0].for_each_take_mut_rev(2, |i| *i += 100);
+    assert_eq!(collect(&nodes[0]), vec![110, 11, 12, 3, 4, 5, 6, 7, 8, 109]);
+}
+
+#[test]
+fn reverse() {
+    let mut node0 = LinkNode::new(0);
+    node0.reverse();
+    assert_eq!(collect(&node0), vec![0]);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    nodes[3].reverse();
+    assert_eq!(collect(&nodes[9]), (0..10).rev().collect::<Vec<_>>());
+    assert_eq!(collect_rev(&nodes[0]), (0..10).collect::<Vec<_>>());
+    nodes[0].reverse();
+    assert_eq!(collect(&nodes[0]), (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn for_each_step_by() {
+    let node0 = LinkNode::new(0);
+    let mut vec = vec![];
+    node0.for_each_step_by(NonZeroUsize::new(3).unwrap(), |&i| vec.push(i));
+    assert_eq!(vec, vec![0]);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    for (step, expected) in [
+        (2, vec![0, 2, 4, 6, 8]),
+        (3, vec![0, 3, 6, 9]),
+        (7, vec![0, 7]),
+    ] {
+        vec.clear();
+        nodes[0].for_each_step_by(NonZeroUsize::new(step).unwrap(), |&i| vec.push(i));
+        assert_eq!(vec, expected);
+    }
+    nodes[1].for_each_step_by_mut(NonZeroUsize::new(2).unwrap(), |i| *i = 0);
+    assert_eq!(collect(&nodes[0]), vec![0, 0, 2, 0, 4, 0, 6, 0, 8, 0]);
+}
+
+#[test]
+fn cursor_remove() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    let mut cursor = unsafe { nodes[0].cursor_mut() };
+    let mut keep = true;
+    while cursor.current().is_some() {
+        if keep {
+            cursor.move_next();
+        } else {
+            assert!(cursor.remove_current());
+        }
+        keep = !keep;
+    }
+    assert_eq!(collect(&nodes[0]), vec![0, 2, 4, 6, 8]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 8, 6, 4, 2]);
+    for i in [1, 3, 5, 7, 9] {
+        assert!(nodes[i].is_standalone());
+    }
+
+    // remove everything, including the anchor
+    let mut cursor = unsafe { nodes[4].cursor_mut() };
+    let mut removed = 0;
+    while cursor.current().is_some() {
+        if cursor.remove_current() {
+            removed += 1;
+        }
+    }
+    assert_eq!(removed, 4);
+    assert!(nodes.iter().all(LinkNode::is_standalone));
+}
+
+#[test]
+fn cursor_move() {
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let mut cursor = unsafe { nodes[1].cursor_mut() };
+    assert_eq!(cursor.current(), Some(&mut 1));
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some(&mut 0));
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 2));
+}
+
+#[test]
+fn cursor_insert() {
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let (n0, n1) = nodes.split_at_mut(3);
+    let mut cursor = unsafe { n0[0].cursor_mut() };
+    cursor.move_next();
+    cursor.insert_after(&mut n1[0]);
+    assert_eq!(cursor.current(), Some(&mut 1));
+    cursor.move_next();
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    cursor.insert_after(&mut n1[1]);
+    // inserting the current node is a no-op
+    cursor.move_prev();
+    cursor.insert_after(&mut n1[1]);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 3, 2, 4]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 4, 2, 3, 1]);
+
+    // inserting the new anchor at the end position is a no-op
+    let (n0, n1) = nodes.split_at_mut(1);
+    let mut cursor = unsafe { n0[0].cursor_mut() };
+    assert!(cursor.remove_current());
+    cursor.move_prev();
+    assert_eq!(cursor.current(), None);
+    cursor.insert_after(&mut n1[0]);
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some(&mut 1));
+    assert!(nodes[0].is_standalone());
+    assert_eq!(collect(&nodes[1]), vec![1, 3, 2, 4]);
+    assert_eq!(collect_rev(&nodes[1]), vec![1, 4, 2, 3]);
+}
+
+#[test]
+fn clone() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let cloned = nodes[2].clone_list();
+    nodes[0].for_each_mut(|i| *i *= 10);
+    assert_eq!(collect(&nodes[0]), vec![0, 10, 20, 30, 40]);
+    assert_eq!(cloned.len(), 5);
+    assert_eq!(collect(&cloned[0]), vec![2, 3, 4, 0, 1]);
+    assert_eq!(collect_rev(&cloned[0]), vec![2, 1, 0, 4, 3]);
+}
+
+#[test]
+fn for_each_node() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let mut ids = HashMap::new();
+    nodes[2].for_each_node(|node| {
+        assert_eq!(node.is_start(), node.index() == 0);
+        assert_eq!(*node.data(), (node.index() + 2) % 5);
+        ids.insert(node.id(), *node.data());
+    });
+    assert_eq!(ids.len(), 5);
+    for node in &nodes {
+        assert_eq!(ids[&node.id()], **node);
+    }
+}
+
+#[test]
+fn for_each_chunks() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    for (n, expected) in [
+        (
+            3,
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8], vec![9]],
+        ),
+        (1, (0..10).map(|i| vec![i]).collect()),
+        (20, vec![(0..10).collect()]),
+    ] {
+        let mut chunks = vec![];
+        nodes[0].for_each_chunks(NonZeroUsize::new(n).unwrap(), |chunk| {
+            chunks.push(chunk.iter().map(|&&i| i).collect::<Vec<_>>())
+        });
+        assert_eq!(chunks, expected);
+    }
+    nodes[0].for_each_chunks_mut(NonZeroUsize::new(4).unwrap(), |chunk| {
+        let len = chunk.len();
+        for i in chunk.iter_mut() {
+            **i = len;
+        }
+    });
+    assert_eq!(collect(&nodes[0]), vec![4, 4, 4, 4, 4, 4, 4, 4, 2, 2]);
+}
+
+#[test]
+fn debug() {
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    assert_eq!(format!("{:?}", nodes[0]), "[0]");
+    connect_all(&mut nodes, 0, 3);
+    assert_eq!(format!("{:?}", nodes[0]), "[0, 1, 2]");
+    assert_eq!(format!("{:?}", nodes[1]), "[1, 2, 0]");
+}
+
+#[test]
+fn to_vec() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    assert_eq!(nodes[0].to_vec(), vec![0]);
+    connect_all(&mut nodes, 0, 10);
+    for node in &nodes {
+        assert_eq!(node.to_vec(), collect(node));
+    }
+    let strings = LinkNode::new(String::from("a"));
+    assert_eq!(strings.to_vec(), vec![String::from("a")]);
+}
+
+#[test]
+fn zip_for_each() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let mut names = ["a", "b", "c"].map(LinkNode::new);
+    connect_all(&mut names, 0, 3);
+    let mut pairs = vec![];
+    nodes[0].zip_for_each(&names[0], |&i, &s| pairs.push((i, s)));
+    assert_eq!(pairs, vec![(0, "a"), (1, "b"), (2, "c")]);
+    pairs.clear();
+    nodes[3].zip_for_each(&names[1], |&i, &s| pairs.push((i, s)));
+    assert_eq!(pairs, vec![(3, "b"), (4, "c"), (0, "a")]);
+
+    let single = LinkNode::new("z");
+    pairs.clear();
+    nodes[0].zip_for_each(&single, |&i, &s| pairs.push((i, s)));
+    assert_eq!(pairs, vec![(0, "z")]);
+
+    names[2].zip_for_each_mut(&nodes[1], |s, &i| {
+        if i % 2 == 0 {
+            *s = "even";
+        }
+    });
+    assert_eq!(names[0].to_vec(), vec!["even", "b", "c"]);
+}
+
+#[test]
+fn zip_for_each_mut_same_list() {
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let (n0, n1) = nodes.split_at_mut(1);
+    n0[0].zip_for_each_mut(&n1[1], |a, &b| *a = b * 10);
+    assert_eq!(collect(&nodes[0]), vec![20, 200, 2000]);
+}
+
+#[test]
+fn try_for_each() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    let mut visited = 0;
+    let result = nodes[0].try_for_each(|&i| {
+        visited += 1;
+        if i == 3 {
+            ControlFlow::Break(i * 10)
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert_eq!(result, ControlFlow::Break(30));
+    assert_eq!(visited, 4);
+
+    visited = 0;
+    let result = nodes[0].try_for_each(|_| {
+        visited += 1;
+        ControlFlow::<()>::Continue(())
+    });
+    assert_eq!(result, ControlFlow::Continue(()));
+    assert_eq!(visited, 10);
+
+    // break on the last node before wrapping around
+    let mut seen = vec![];
+    let result = nodes[3].try_for_each_rev(|&i| {
+        seen.push(i);
+        if i == 4 {
+            ControlFlow::Break("found")
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert_eq!(result, ControlFlow::Break("found"));
+    assert_eq!(seen, vec![3, 2, 1, 0, 9, 8, 7, 6, 5, 4]);
+
+    visited = 0;
+    let result = nodes[5].try_for_each_mut(|i| {
+        visited += 1;
+        *i += 100;
+        if *i == 107 {
+            ControlFlow::Break(String::from("seven"))
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    assert_eq!(result, ControlFlow::Break(String::from("seven")));
+    assert_eq!(visited, 3);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2, 3, 4, 105, 106, 107, 8, 9]);
+
+    visited = 0;
+    let result = nodes[1].try_for_each_mut_rev(|i| {
+        visited += 1;
+        *i += 1;
+        ControlFlow::Break(*i)
+    });
+    assert_eq!(result, ControlFlow::Break(2));
+    assert_eq!(visited, 1);
+    assert_eq!(collect(&nodes[0]), vec![0, 2, 2, 3, 4, 105, 106, 107, 8, 9]);
+
+    let node = LinkNode::new(0);
+    assert_eq!(
+        node.try_for_each_rev(|&i| ControlFlow::Break(i)),
+        ControlFlow::Break(0)
+    );
+}
+
+#[test]
+fn raw_handles() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    // stash handles in a side table, then navigate from them later
+    let table = unsafe { nodes[0].raw_iter() }.collect::<Vec<_>>();
+    assert_eq!(table.len(), 5);
+    assert_eq!(table[2], nodes[2].as_raw());
+    for (i, &handle) in table.iter().enumerate() {
+        unsafe {
+            assert_eq!(*LinkNode::data_from_raw(handle), i);
+            assert_eq!(LinkNode::next_raw(handle), table[(i + 1) % 5]);
+            assert_eq!(LinkNode::prev_raw(handle), table[(i + 4) % 5]);
+        }
+    }
+    let rev = unsafe { nodes[0].raw_iter() }.rev().collect::<Vec<_>>();
+    assert_eq!(rev[0], table[4]);
+}
+
+#[test]
+fn find() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    let mut visited = 0;
+    let found = nodes[0].find(
+        |&i| {
+            visited += 1;
+            i > 4
+        },
+        |&i| i,
+    );
+    assert_eq!(found, Some(5));
+    assert_eq!(visited, 6);
+    assert_eq!(nodes[7].find(|&i| i % 7 == 0, |&i| i), Some(7));
+    assert_eq!(nodes[0].find(|&i| i > 10, |&i| i), None);
+
+    // match at the last node
+    assert_eq!(nodes[3].find(|&i| i == 2, |&i| i * 10), Some(20));
+    // rfind starts from the node before self and ends at self
+    visited = 0;
+    let found = nodes[3].rfind(
+        |&i| {
+            visited += 1;
+            i % 2 == 1
+        },
+        |&i| i,
+    );
+    assert_eq!(found, Some(1));
+    assert_eq!(visited, 2);
+    assert_eq!(nodes[3].rfind(|&i| i == 3, |&i| i), Some(3));
+    assert_eq!(nodes[3].rfind(|&i| i > 10, |&i| i), None);
+
+    visited = 0;
+    let found = nodes[8].find_map(|&i| {
+        visited += 1;
+        (i < 5).then(|| i.to_string())
+    });
+    assert_eq!(found.as_deref(), Some("0"));
+    assert_eq!(visited, 3);
+    assert_eq!(nodes[8].find_map(|&i| (i == 8).then_some(i * 10)), Some(80));
+    assert_eq!(nodes[8].find_map(|&i| (i == 7).then_some(i)), Some(7));
+    assert_eq!(nodes[8].find_map(|&i| (i > 10).then_some(i)), None);
+}
+
+#[test]
+fn nth() {
+    let mut node0 = LinkNode::new(0);
+    assert_eq!(node0.nth(0, |&i| i), Some(0));
+    assert_eq!(node0.nth_back(0, |&i| i), Some(0));
+    assert_eq!(node0.nth(1, |&i| i), None);
+    assert_eq!(node0.nth_mut(0, |i| *i = 10), Some(()));
+    assert_eq!(node0.nth_back_mut(1, |i| *i = 20), None);
+    assert_eq!(*node0, 10);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    assert_eq!(nodes[3].nth(3, |&i| i), Some(6));
+    assert_eq!(nodes[3].nth(9, |&i| i), Some(2));
+    assert_eq!(nodes[3].nth(10, |&i| i), None);
+    assert_eq!(nodes[3].nth_back(3, |&i| i), Some(0));
+    assert_eq!(nodes[3].nth_back(9, |&i| i), Some(4));
+    assert_eq!(nodes[3].nth_back(10, |&i| i), None);
+    nodes[0].nth_mut(2, |i| *i = 20).unwrap();
+    nodes[0].nth_back_mut(2, |i| *i = 80).unwrap();
+    assert_eq!(nodes[0].nth_mut(10, |i| *i = 100), None);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 20, 3, 4, 5, 6, 7, 80, 9]);
+}
+
+#[test]
+fn position() {
+    let node0 = LinkNode::new(0);
+    assert_eq!(node0.position(|&i| i == 0), Some(0));
+    assert_eq!(node0.position(|&i| i == 1), None);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    assert_eq!(nodes[0].position(|&i| i == 3), Some(3));
+    assert_eq!(nodes[5].position(|&i| i == 3), Some(8));
+    assert_eq!(nodes[5].position(|&i| i == 10), None);
+    assert_eq!(nodes[5].position(|&i| i == 4), Some(9));
+
+    assert_eq!(node0.rposition(|&i| i == 0), Some(0));
+    assert_eq!(nodes[5].rposition(|&i| i == 5), Some(0));
+    assert_eq!(nodes[5].rposition(|&i| i == 3), Some(2));
+    assert_eq!(nodes[5].rposition(|&i| i == 6), Some(9));
+    assert_eq!(nodes[5].rposition(|&i| i == 10), None);
+    let mut visited = 0;
+    nodes[5].rposition(|&i| {
+        visited += 1;
+        i < 5
+    });
+    assert_eq!(visited, 2);
+}
+
+#[test]
+fn for_each_while() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    // false on the first element
+    assert_eq!(nodes[0].for_each_while(|_| false), 1);
+    assert_eq!(nodes[0].for_each_until(|_| true), 1);
+    // never false
+    assert_eq!(nodes[0].for_each_while(|_| true), 10);
+    assert_eq!(nodes[0].for_each_until_rev(|_| false), 10);
+    // false exactly at the wrap-around boundary
+    assert_eq!(nodes[3].for_each_while(|&i| i != 2), 10);
+    assert_eq!(nodes[3].for_each_while_rev(|&i| i != 4), 10);
+    assert_eq!(nodes[3].for_each_until(|&i| i == 0), 8);
+
+    assert_eq!(
+        nodes[0].for_each_while_mut(|i| {
+            *i += 10;
+            *i < 12
+        }),
+        3
+    );
+    assert_eq!(
+        nodes[0].for_each_until_mut_rev(|i| {
+            *i += 100;
+            *i < 110
+        }),
+        2
+    );
+    assert_eq!(nodes[0].for_each_while_mut_rev(|_| false), 1);
+    assert_eq!(nodes[0].for_each_until_mut(|_| false), 10);
+    assert_eq!(collect(&nodes[0]), vec![110, 11, 12, 3, 4, 5, 6, 7, 8, 109]);
+}
+
+#[test]
+fn same_list() {
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    connect_all(&mut nodes, 3, 6);
+    assert!(nodes[0].same_list(&nodes[0]));
+    assert!(nodes[0].same_list(&nodes[2]));
+    assert!(nodes[2].same_list(&nodes[0]));
+    assert!(!nodes[0].same_list(&nodes[3]));
+    assert!(!nodes[5].same_list(&nodes[1]));
+}
+
+#[test]
+fn fold() {
+    let node0 = LinkNode::new(1);
+    assert_eq!(node0.fold(0, |acc, &i| acc + i), 1);
+    assert_eq!(node0.rfold(0, |acc, &i| acc + i), 1);
+
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    assert_eq!(nodes[2].fold(0, |acc, &i| acc + i), 10);
+    assert_eq!(
+        nodes[2].fold(String::new(), |acc, i| acc + &i.to_string()),
+        "23401"
+    );
+    assert_eq!(
+        nodes[2].rfold(String::new(), |acc, i| acc + &i.to_string()),
+        "10432"
+    );
+
+    // each node is visited exactly once
+    let visits = nodes[3].fold(vec![0; 5], |mut visits, &i| {
+        visits[i] += 1;
+        visits
+    });
+    assert_eq!(visits, vec![1; 5]);
+    let visits = nodes[3].rfold(vec![0; 5], |mut visits, &i| {
+        visits[i] += 1;
+        visits
+    });
+    assert_eq!(visits, vec![1; 5]);
+    assert_eq!(nodes[1].fold(1, |acc, &i| acc * (i + 1)), 120);
+    assert_eq!(nodes[1].rfold(0, |acc, &i| acc.max(i)), 4);
+}
+
+#[test]
+fn try_fold() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let ok = nodes[0].try_fold(0, |acc, &i| Ok::<_, String>(acc + i));
+    assert_eq!(ok, Ok(10));
+    let mut visited = 0;
+    let err = nodes[0].try_fold(0, |acc, &i| {
+        visited += 1;
+        if acc + i > 3 {
+            Err(format!("budget exceeded at {i}"))
+        } else {
+            Ok(acc + i)
+        }
+    });
+    assert_eq!(err, Err(String::from("budget exceeded at 3")));
+    assert_eq!(visited, 4);
+}
+
+#[test]
+fn for_each_cycles() {
+    let mut node0 = LinkNode::new(0);
+    let mut visited = 0;
+    node0.for_each_cycles(0, |_| visited += 1);
+    assert_eq!(visited, 0);
+    node0.for_each_cycles_mut(3, |i| *i += 1);
+    assert_eq!(*node0, 3);
+
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let mut vec = vec![];
+    nodes[1].for_each_cycles(2, |&i| vec.push(i));
+    assert_eq!(vec, vec![1, 2, 0, 1, 2, 0]);
+    nodes[1].for_each_cycles_mut(2, |i| *i += 10);
+    assert_eq!(collect(&nodes[0]), vec![20, 21, 22]);
+}
+
+#[test]
+fn into_inner() {
+    let node0 = LinkNode::new(String::from("a"));
+    assert_eq!(node0.into_inner(), "a");
+
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let node2 = nodes.remove(2);
+    assert_eq!(node2.into_inner(), 2);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 3, 4]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 4, 3, 1]);
+}
+
+#[test]
+fn for_each_detach() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    assert_eq!(nodes[0].for_each_detach(|i| *i % 2 == 1), 5);
+    assert_eq!(collect(&nodes[0]), vec![0, 2, 4, 6, 8]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 8, 6, 4, 2]);
+    assert!(nodes[1].is_standalone());
+
+    // the last node
+    assert_eq!(nodes[0].for_each_detach(|i| *i == 8), 1);
+    assert_eq!(collect(&nodes[0]), vec![0, 2, 4, 6]);
+
+    // all nodes but the anchor
+    assert_eq!(nodes[2].for_each_detach(|_| true), 3);
+    assert!(nodes.iter().all(LinkNode::is_standalone));
+    assert_eq!(nodes[2].for_each_detach(|_| true), 0);
+}
+
+#[test]
+fn from() {
+    let node0 = LinkNode::from(0);
+    let node1: LinkNode<i32> = 1.into();
+    assert_eq!((*node0, *node1), (0, 1));
+    let mut nodes = (0..3).map(LinkNode::from).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2]);
+}
+
+#[test]
+fn default() {
+    let node0 = LinkNode::<i32>::default();
+    assert_eq!(*node0, 0);
+    assert!(node0.is_standalone());
+}
+
+#[test]
+fn for_each_from() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    for (n, expected) in [(0, 10), (9, 1), (10, 0), (20, 0)] {
+        let mut vec = vec![];
+        nodes[2].for_each_from(n, |&i| vec.push(i));
+        assert_eq!(vec, collect(&nodes[2])[n.min(10)..]);
+        assert_eq!(vec.len(), expected);
+        vec.clear();
+        nodes[2].for_each_from_rev(n, |&i| vec.push(i));
+        assert_eq!(vec, collect_rev(&nodes[2])[n.min(10)..]);
+    }
+    nodes[0].for_each_from_mut(8, |i| *i += 10);
+    nodes[0].for_each_from_mut_rev(9, |i| *i += 100);
+    assert_eq!(collect(&nodes[0]), vec![0, 101, 2, 3, 4, 5, 6, 7, 18, 19]);
+}
+
+#[test]
+fn count() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 6);
+    connect_all(&mut nodes, 6, 10);
+    let check = |nodes: &[LinkNode<usize>]| {
+        for node in nodes {
+            assert_eq!(node.len(), node.count());
+            assert_eq!(node.count(), collect(node).len());
+        }
+    };
+    check(&nodes);
+    nodes[3].take();
+    check(&nodes);
+    let (n0, n1) = nodes.split_at_mut(6);
+    n0[0].append(&mut n1[0]);
+    check(&nodes);
+    assert_eq!(nodes[0].count(), 9);
+    let (n0, n1) = nodes.split_at_mut(4);
+    n1[0].split_off(&mut n0[0]);
+    check(&nodes);
+    assert_eq!(nodes[0].count(), 7);
+    assert_eq!(nodes[4].count(), 2);
+}
+
+#[test]
+fn for_each_prefetch() {
+    let node0 = LinkNode::new(0);
+    let mut vec = vec![];
+    node0.for_each_prefetch(|&i| vec.push(i));
+    assert_eq!(vec, vec![0]);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    for node in &nodes {
+        vec.clear();
+        node.for_each_prefetch(|&i| vec.push(i));
+        assert_eq!(vec, collect(node));
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Vec<String>,
+    stop_at: Option<usize>,
+}
+
+impl RingVisitor<i32> for Recorder {
+    fn begin(&mut self, len_hint: Option<usize>) {
+        self.events.push(format!("begin {len_hint:?}"));
+    }
+
+    fn visit(&mut self, index: usize, data: &i32) -> ControlFlow<()> {
+        self.events.push(format!("{index}: {data}"));
+        if self.stop_at == Some(index) {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn finish(&mut self) {
+        self.events.push("finish".to_string());
+    }
+}
+
+impl RingVisitorMut<i32> for Recorder {
+    fn begin(&mut self, len_hint: Option<usize>) {
+        RingVisitor::begin(self, len_hint);
+    }
+
+    fn visit(&mut self, index: usize, data: &mut i32) -> ControlFlow<()> {
+        *data *= 10;
+        RingVisitor::visit(self, index, data)
+    }
+
+    fn finish(&mut self) {
+        RingVisitor::finish(self);
+    }
+}
+
+#[test]
+fn accept() {
+    let mut nodes = (0..4).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 4);
+
+    let mut recorder = Recorder::default();
+    assert!(nodes[2].accept(&mut recorder).is_continue());
+    assert_eq!(
+        recorder.events,
+        ["begin None", "0: 2", "1: 3", "2: 0", "3: 1", "finish"]
+    );
+
+    let mut recorder = Recorder {
+        stop_at: Some(1),
+        ..Default::default()
+    };
+    assert!(nodes[0].accept(&mut recorder).is_break());
+    assert_eq!(recorder.events, ["begin None", "0: 0", "1: 1", "finish"]);
+
+    let mut counter = Counter::new();
+    assert!(nodes[1].accept(&mut counter).is_continue());
+    assert_eq!(counter.count(), 4);
+
+    let mut collector = Collector::new();
+    assert!(nodes[1].accept(&mut collector).is_continue());
+    assert_eq!(collector.into_items(), vec![1, 2, 3, 0]);
+
+    let mut collector = Collector::with_limit(0);
+    assert!(nodes[1].accept(&mut collector).is_break());
+    assert!(collector.items().is_empty());
+}
+
+#[test]
+fn accept_mut() {
+    let mut nodes = (0..4).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 4);
+
+    let mut recorder = Recorder {
+        stop_at: Some(2),
+        ..Default::default()
+    };
+    assert!(nodes[1].accept_mut(&mut recorder).is_break());
+    assert_eq!(
+        recorder.events,
+        ["begin None", "0: 10", "1: 20", "2: 30", "finish"]
+    );
+    assert_eq!(collect(&nodes[0]), vec![0, 10, 20, 30]);
+
+    let mut counter = Counter::new();
+    assert!(nodes[0].accept_mut(&mut counter).is_continue());
+    assert_eq!(counter.count(), 4);
+
+    let mut collector = Collector::with_limit(2);
+    assert!(nodes[3].accept_mut(&mut collector).is_break());
+    assert_eq!(collector.items(), &[30, 0]);
+}
+
+#[test]
+fn try_new() {
+    let mut node0 = LinkNode::try_new(0).unwrap();
+    let mut node1 = LinkNode::try_new(1).unwrap();
+    assert!(node0.is_standalone());
+    node0.add(&mut node1);
+    assert_eq!(collect(&node0), vec![0, 1]);
+
+    let node = LinkNode::try_new(String::from("data")).unwrap();
+    assert_eq!(*node, "data");
+    assert_eq!(node.into_inner(), "data");
+
+    let node = LinkNode::try_new(()).unwrap();
+    assert_eq!(node.len(), 1);
+}
+
+#[test]
+fn try_for_each_err() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let check = |limit: i32| {
+        move |&i: &i32| {
+            if i < limit {
+                Ok(())
+            } else {
+                Err(format!("{i} is too large"))
+            }
+        }
+    };
+
+    // error on the first node
+    let mut visited = 0;
+    let result = nodes[3].try_for_each_err(|i| {
+        visited += 1;
+        check(3)(i)
+    });
+    assert_eq!(result, Err("3 is too large".to_string()));
+    assert_eq!(visited, 1);
+
+    // error on the last node before wrapping around
+    visited = 0;
+    let result = nodes[1].try_for_each_err(|i| {
+        visited += 1;
+        if *i == 0 {
+            Err("wrapped around")
+        } else {
+            Ok(())
+        }
+    });
+    assert_eq!(result, Err("wrapped around"));
+    assert_eq!(visited, 5);
+
+    // no error
+    assert_eq!(nodes[2].try_for_each_err(check(5)), Ok(()));
+
+    // errors convert with `?`
+    fn parse_all(node: &mut LinkNode<String>) -> Result<i32, Box<dyn std::error::Error>> {
+        let mut sum = 0;
+        node.try_for_each_err_mut(|s| {
+            s.push('0');
+            sum += s.parse::<i32>()?;
+            Ok::<_, std::num::ParseIntError>(())
+        })?;
+        Ok(sum)
+    }
+    let mut node0 = LinkNode::new("1".to_string());
+    let mut node1 = LinkNode::new("2".to_string());
+    node0.add(&mut node1);
+    assert_eq!(parse_all(&mut node0).unwrap(), 30);
+    assert_eq!(node0.to_vec(), vec!["10", "20"]);
+    let mut node2 = LinkNode::new("x".to_string());
+    node0.add(&mut node2);
+    assert!(parse_all(&mut node0).is_err());
+    assert_eq!(node0.to_vec(), vec!["100", "x0", "20"]);
+}
+
+struct Task {
+    id: u32,
+    ready: Link,
+    timer: Link,
+}
+
+link_adapter!(ReadyList = Task { ready });
+link_adapter!(TimerList = Task { timer });
+
+/// Boxed and pinned, so that the tasks never move while linked.
+fn new_task(id: u32) -> Pin<Box<Task>> {
+    Box::pin(Task {
+        id,
+        ready: Link::new(),
+        timer: Link::new(),
+    })
+}
+
+fn collect_ids<A: Adapter<Value = Task>>(task: &Task) -> Vec<u32> {
+    let mut ids = vec![];
+    A::for_each(task, |task| ids.push(task.id));
+    ids
+}
+
+#[test]
+fn multiple_links() {
+    let tasks = (0..5).map(new_task).collect::<Vec<_>>();
+    assert!(!ReadyList::is_linked(&tasks[0]));
+    assert_eq!(collect_ids::<ReadyList>(&tasks[0]), vec![0]);
+
+    for i in 1..5 {
+        unsafe { ReadyList::add_before(tasks[0].as_ref(), tasks[i].as_ref()) };
+    }
+    unsafe { TimerList::add(tasks[3].as_ref(), tasks[1].as_ref()) };
+    unsafe { TimerList::add(tasks[3].as_ref(), tasks[4].as_ref()) };
+    assert_eq!(collect_ids::<ReadyList>(&tasks[2]), vec![2, 3, 4, 0, 1]);
+    assert_eq!(collect_ids::<TimerList>(&tasks[3]), vec![3, 4, 1]);
+    assert!(!TimerList::is_linked(&tasks[0]));
+    assert_eq!(TimerList::len(&tasks[0]), 1);
+
+    // moving a task within one list leaves the other untouched
+    unsafe { ReadyList::add(tasks[0].as_ref(), tasks[4].as_ref()) };
+    assert_eq!(collect_ids::<ReadyList>(&tasks[0]), vec![0, 4, 1, 2, 3]);
+    assert_eq!(collect_ids::<TimerList>(&tasks[4]), vec![4, 1, 3]);
+
+    // adding a task to itself does nothing
+    unsafe { ReadyList::add(tasks[1].as_ref(), tasks[1].as_ref()) };
+    assert_eq!(ReadyList::len(&tasks[1]), 5);
+
+    ReadyList::remove(&tasks[1]);
+    assert!(!ReadyList::is_linked(&tasks[1]));
+    assert!(TimerList::is_linked(&tasks[1]));
+    assert_eq!(collect_ids::<ReadyList>(&tasks[0]), vec![0, 4, 2, 3]);
+
+    // dropping a task removes it from both lists
+    let mut tasks = tasks;
+    drop(tasks.remove(4));
+    assert_eq!(collect_ids::<ReadyList>(&tasks[0]), vec![0, 2, 3]);
+    assert_eq!(collect_ids::<TimerList>(&tasks[1]), vec![1, 3]);
+    drop(tasks.remove(3));
+    assert!(!TimerList::is_linked(&tasks[1]));
+    assert_eq!(collect_ids::<ReadyList>(&tasks[2]), vec![2, 0]);
+}
+
+#[test]
+fn multiple_links_remove_during_walk() {
+    let tasks = (0..4).map(new_task).collect::<Vec<_>>();
+    for task in &tasks[1..] {
+        unsafe { ReadyList::add_before(tasks[0].as_ref(), task.as_ref()) };
+    }
+    let mut ids = vec![];
+    ReadyList::for_each(&tasks[0], |task| {
+        ids.push(task.id);
+        if task.id == 1 {
+            ReadyList::remove(task);
+        }
+    });
+    assert_eq!(ids, vec![0, 1]);
+    assert_eq!(collect_ids::<ReadyList>(&tasks[0]), vec![0, 2, 3]);
+}
+
+#[test]
+fn list_push_pop() {
+    let mut list = List::new();
+    assert!(list.is_empty());
+    assert_eq!(list.pop_front(), None);
+    assert_eq!(list.pop_back(), None);
+    assert_eq!(list.iter().next(), None);
+
+    for i in 0..3 {
+        list.push_back(i);
+        list.push_front(-i - 1);
+    }
+    assert_eq!(list.len(), 6);
+    assert_eq!(
+        list.iter().copied().collect::<Vec<_>>(),
+        vec![-3, -2, -1, 0, 1, 2]
+    );
+    assert_eq!(
+        list.iter().rev().copied().collect::<Vec<_>>(),
+        vec![2, 1, 0, -1, -2, -3]
+    );
+    assert_eq!(list.iter().len(), 6);
+
+    for i in &mut list {
+        *i *= 10;
+    }
+    assert_eq!(format!("{list:?}"), "[-30, -20, -10, 0, 10, 20]");
+
+    assert_eq!(list.pop_front(), Some(-30));
+    assert_eq!(list.pop_back(), Some(20));
+    assert_eq!(list.pop_back(), Some(10));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![-20, -10, 0]);
+
+    assert_eq!(list.pop_back(), Some(0));
+    assert_eq!(list.pop_front(), Some(-20));
+    assert_eq!(list.pop_front(), Some(-10));
+    assert_eq!(list.pop_front(), None);
+    assert!(list.is_empty());
+
+    // reusable after being emptied
+    list.push_back(1);
+    assert_eq!(list.pop_back(), Some(1));
+}
+
+#[test]
+fn list_drop() {
+    let counter = Rc::new(());
+    let mut list = List::new();
+    for _ in 0..5 {
+        list.push_back(Rc::clone(&counter));
+    }
+    drop(list.pop_front());
+    assert_eq!(Rc::strong_count(&counter), 5);
+    drop(list);
+    assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn find_mut() {
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 6);
+    let mut visited = 0;
+    let found = nodes[4].find_mut(
+        |&i| {
+            visited += 1;
+            i % 2 == 1
+        },
+        |i| {
+            *i += 10;
+            *i
+        },
+    );
+    assert_eq!(found, Some(15));
+    assert_eq!(visited, 2);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2, 3, 4, 15]);
+
+    // no match leaves the ring untouched
+    assert!(nodes[0].find_mut(|&i| i > 20, |i| *i = 0).is_none());
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2, 3, 4, 15]);
+
+    nodes[2].find_mut(|&i| i == 1, |i| *i = 7).unwrap();
+    assert_eq!(collect(&nodes[0]), vec![0, 7, 2, 3, 4, 15]);
+}
+
+#[test]
+fn list_extend() {
+    let mut list = List::new();
+    list.extend((0..5).map(LinkNode::new));
+    assert_eq!(list.len(), 5);
+    assert_eq!(
+        list.iter().copied().collect::<Vec<_>>(),
+        vec![0, 1, 2, 3, 4]
+    );
+
+    list.extend(5..7);
+    assert_eq!(list.len(), 7);
+
+    // linked nodes are taken out of their list
+    let mut nodes = (10..13).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let node = nodes.remove(1);
+    list.extend([node]);
+    assert_eq!(collect(&nodes[0]), vec![10, 12]);
+    assert_eq!(list.len(), 8);
+    assert_eq!(list.pop_back(), Some(11));
+    assert_eq!(list.pop_back(), Some(6));
+    assert_eq!(list.pop_front(), Some(0));
+}
+
+#[test]
+fn map() {
+    let mut nodes = (0..3).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    let list = nodes[0].map(|&x| x * 10);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 10, 20]);
+
+    let list = nodes[1].map(|&x| x.to_string());
+    drop(nodes);
+    assert_eq!(list.iter().collect::<Vec<_>>(), vec!["1", "2", "0"]);
+
+    assert_eq!(LinkNode::new(1).map(|&x| x + 1).pop_front(), Some(2));
+}
+
+#[test]
+fn any_all() {
+    let node = LinkNode::new(3);
+    let mut calls = 0;
+    assert!(node.any(|&i| {
+        calls += 1;
+        i == 3
+    }));
+    assert!(!node.all(|&i| {
+        calls += 1;
+        i != 3
+    }));
+    assert_eq!(calls, 2);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    calls = 0;
+    assert!(nodes[0].any(|&i| {
+        calls += 1;
+        i == 2
+    }));
+    assert_eq!(calls, 3);
+    calls = 0;
+    assert!(!nodes[0].all(|&i| {
+        calls += 1;
+        i < 4
+    }));
+    assert_eq!(calls, 5);
+
+    // no match walks the whole ring
+    calls = 0;
+    assert!(!nodes[4].any(|_| {
+        calls += 1;
+        false
+    }));
+    assert_eq!(calls, 10);
+    calls = 0;
+    assert!(nodes[4].all(|_| {
+        calls += 1;
+        true
+    }));
+    assert_eq!(calls, 10);
+}
+
+#[test]
+fn partial_eq() {
+    let mut a = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    let mut b = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    assert_eq!(a[0], b[0]);
+    assert_ne!(a[0], b[1]);
+    connect_all(&mut a, 0, 5);
+    connect_all(&mut b, 0, 5);
+    assert_eq!(a[0], a[0]);
+    assert_eq!(a[0], b[0]);
+    assert_eq!(a[3], b[3]);
+    assert_ne!(a[0], b[1]);
+    assert_ne!(a[0], a[1]);
+
+    // differing in one element
+    *b[2] = 20;
+    assert_ne!(a[0], b[0]);
+    *b[2] = 2;
+
+    // different lengths
+    b[4].take();
+    assert_ne!(a[0], b[0]);
+    assert_ne!(b[0], a[0]);
+    a[4].take();
+    assert_eq!(a[0], b[0]);
+    assert_eq!(a[4], b[4]);
+
+    // a copy is equal when read from the node matching the start
+    let copy = a[2].clone_list();
+    assert_eq!(copy[0], a[2]);
+    assert_eq!(copy[1], a[3]);
+    assert_ne!(copy[0], a[0]);
+}
+
+#[test]
+fn contains() {
+    let node = LinkNode::new(7);
+    assert!(node.contains(&7));
+    assert!(!node.contains(&0));
+
+    let mut nodes = (0..1000).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 1000);
+    assert!(nodes[500].contains(&500));
+    assert!(nodes[500].contains(&499));
+    assert!(!nodes[500].contains(&1000));
+    assert!(nodes[10].contains_by(|&i| i % 999 == 998));
+    assert!(!nodes[10].contains_by(|&i| i < 0));
+
+    let mut words = ["a", "bc", "def"]
+        .map(|s| LinkNode::new(s.to_string()))
+        .into_iter()
+        .collect::<Vec<_>>();
+    connect_all(&mut words, 0, 3);
+    assert!(words[1].contains(&"a".to_string()));
+    assert!(!words[1].contains(&"b".to_string()));
+    assert!(words[0].contains_by(|s| s.len() == 3));
+}
+
+#[test]
+fn ptr_eq() {
+    let mut node0 = LinkNode::new(0);
+    let mut node1 = LinkNode::new(0);
+    assert!(LinkNode::ptr_eq(&node0, &node0));
+    assert!(!LinkNode::ptr_eq(&node0, &node1));
+    assert_eq!(node0, node1);
+    node0.add(&mut node1);
+    assert!(!LinkNode::ptr_eq(&node0, &node1));
+
+    let nodes = [node0, node1];
+    assert!(LinkNode::ptr_eq(&nodes[0], nodes.first().unwrap()));
+}
+
+#[test]
+fn max_min_by() {
+    let node = LinkNode::new(3);
+    assert_eq!(node.max_by(i32::cmp, |&i| i), 3);
+    assert_eq!(node.min_by_key(|&i| i, |&i| i), 3);
+
+    // (key, tag) pairs, compared on the key only
+    let mut nodes = [(2, 'a'), (5, 'b'), (1, 'c'), (5, 'd'), (1, 'e'), (0, 'f')]
+        .map(LinkNode::new)
+        .into_iter()
+        .collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 6);
+    let key = |&(k, _): &(i32, char)| k;
+    // ties resolve to the first encountered element
+    assert_eq!(nodes[0].max_by_key(key, |&p| p), (5, 'b'));
+    assert_eq!(nodes[2].max_by_key(key, |&p| p), (5, 'd'));
+    assert_eq!(nodes[0].max_by(|a, b| a.0.cmp(&b.0), |&p| p), (5, 'b'));
+    assert_eq!(nodes[1].min_by(|a, b| a.0.cmp(&b.0), |&p| p), (0, 'f'));
+    // extreme at the start
+    assert_eq!(nodes[5].min_by_key(key, |&p| p), (0, 'f'));
+    assert_eq!(nodes[3].max_by_key(key, |&p| p), (5, 'd'));
+    // extreme at the last node before wrapping around
+    assert_eq!(nodes[0].min_by_key(key, |&p| p), (0, 'f'));
+    assert_eq!(nodes[2].max_by_key(|&(k, _)| -k, |&p| p), (0, 'f'));
+    assert_eq!(nodes[4].max_by_key(key, |&p| p), (5, 'b'));
+}
+
+#[test]
+fn reduce() {
+    let node = LinkNode::new(5);
+    assert_eq!(node.reduce(|&i| i * 2, |acc, &i| acc + i), 10);
+
+    let mut nodes = ["a", "b", "c", "d"]
+        .map(LinkNode::new)
+        .into_iter()
+        .collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 4);
+    let concat = |node: &LinkNode<&str>| node.reduce(|s| s.to_string(), |acc, s| acc + s);
+    assert_eq!(concat(&nodes[0]), "abcd");
+    assert_eq!(concat(&nodes[2]), "cdab");
+    // non-commutative, so the order is pinned down
+    assert_eq!(
+        nodes[1].reduce(|s| format!("({s}"), |acc, s| format!("{acc} {s})")),
+        "(b c) d) a)"
+    );
+
+    let mut calls = 0;
+    let total = nodes[3].reduce(
+        |_| 1,
+        |acc, _| {
+            calls += 1;
+            acc + 1
+        },
+    );
+    assert_eq!(total, 4);
+    assert_eq!(calls, 3);
+}
+
+#[test]
+fn count_if() {
+    let node = LinkNode::new(1);
+    assert_eq!(node.count_if(|&i| i == 1), 1);
+    assert_eq!(node.count_others_if(|&i| i == 1), 0);
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    assert_eq!(nodes[3].count_if(|&i| i % 2 == 0), 5);
+    assert_eq!(nodes[4].count_others_if(|&i| i % 2 == 0), 4);
+    // zero matches
+    assert_eq!(nodes[3].count_if(|&i| i > 10), 0);
+    assert_eq!(nodes[3].count_others_if(|&i| i > 10), 0);
+    // all matches
+    assert_eq!(nodes[3].count_if(|_| true), 10);
+    assert_eq!(nodes[3].count_others_if(|_| true), 9);
+    // only the anchor matches
+    assert_eq!(nodes[3].count_if(|&i| i == 3), 1);
+    assert_eq!(nodes[3].count_others_if(|&i| i == 3), 0);
+}
+
+#[test]
+fn is_sorted() {
+    let node = LinkNode::new(0);
+    assert!(node.is_sorted());
+    assert!(node.is_sorted_circular());
+
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    assert!(nodes[0].is_sorted());
+    assert!(!nodes[3].is_sorted());
+    assert!(nodes[3].is_sorted_circular());
+    assert!(nodes[0].is_sorted_by(|a, b| a < b));
+    assert!(!nodes[0].is_sorted_by(|a, b| a > b));
+
+    // one inversion in the middle
+    let mut nodes = [0, 1, 5, 3, 4].map(LinkNode::new);
+    connect_all(&mut nodes, 0, 5);
+    assert!(!nodes[0].is_sorted());
+    assert!(!nodes[0].is_sorted_circular());
+    assert!(!nodes[3].is_sorted());
+
+    // inversion only at the wrap pair
+    let mut nodes = [1, 2, 3, 0].map(LinkNode::new);
+    connect_all(&mut nodes, 0, 4);
+    assert!(!nodes[0].is_sorted());
+    assert!(nodes[0].is_sorted_circular());
+    assert!(nodes[3].is_sorted());
+    assert!(nodes[3].is_sorted_circular());
+
+    let mut equal = [1, 1, 1].map(LinkNode::new);
+    connect_all(&mut equal, 0, 3);
+    assert!(equal[1].is_sorted());
+    assert!(equal[1].is_sorted_circular());
+}
+
+#[test]
+fn lower_bound() {
+    let mut nodes = [1, 2, 4, 4, 4, 7].map(LinkNode::new);
+    connect_all(&mut nodes, 0, 6);
+    assert_eq!(nodes[0].lower_bound(&0), 0);
+    assert_eq!(nodes[0].lower_bound(&1), 0);
+    assert_eq!(nodes[0].lower_bound(&3), 2);
+    assert_eq!(nodes[0].lower_bound(&4), 2);
+    assert_eq!(nodes[0].lower_bound(&5), 5);
+    assert_eq!(nodes[0].lower_bound(&8), 6);
+
+    let mut visited = 0;
+    let index = nodes[0].lower_bound_by(|data| {
+        visited += 1;
+        data.cmp(&2)
+    });
+    assert_eq!((index, visited), (1, 2));
+    assert_eq!(nodes[0].lower_bound_by(|data| (data * 2).cmp(&8)), 2);
+
+    let node = LinkNode::new(3);
+    assert_eq!(node.lower_bound(&2), 0);
+    assert_eq!(node.lower_bound(&4), 1);
+}
+
+#[test]
+fn mismatch() {
+    let eq = |a: &i32, b: &i32| a == b;
+    let mut a = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    let mut b = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut a, 0, 5);
+    connect_all(&mut b, 0, 5);
+    assert_eq!(a[0].mismatch(&b[0], eq), None);
+    assert_eq!(a[2].mismatch(&b[2], eq), None);
+    assert_eq!(a[0].mismatch(&a[0], eq), None);
+
+    // difference at index 0
+    assert_eq!(a[0].mismatch(&b[1], eq), Some(0));
+
+    // common prefix, different lengths
+    b[4].take();
+    assert_eq!(a[0].mismatch(&b[0], eq), Some(4));
+    assert_eq!(b[0].mismatch(&a[0], eq), Some(4));
+    assert_eq!(b[4].mismatch(&a[4], eq), Some(1));
+
+    let words = LinkNode::new("3");
+    assert_eq!(a[3].mismatch(&words, |a, b| a.to_string() == *b), Some(1));
+    assert_eq!(b[4].mismatch(&words, |_, _| true), None);
+}
+
+#[test]
+fn starts_ends_with() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    assert!(nodes[0].starts_with(&[]));
+    assert!(nodes[0].ends_with(&[]));
+    assert!(nodes[0].starts_with(&[0, 1, 2]));
+    assert!(nodes[3].starts_with(&[3, 4, 0]));
+    assert!(nodes[0].ends_with(&[3, 4]));
+    assert!(nodes[2].ends_with(&[4, 0, 1]));
+
+    // exact length
+    assert!(nodes[1].starts_with(&[1, 2, 3, 4, 0]));
+    assert!(nodes[1].ends_with(&[1, 2, 3, 4, 0]));
+
+    // near misses
+    assert!(!nodes[0].starts_with(&[0, 1, 3]));
+    assert!(!nodes[0].starts_with(&[1]));
+    assert!(!nodes[0].ends_with(&[4, 3]));
+    assert!(!nodes[0].ends_with(&[0]));
+
+    // longer than the list
+    assert!(!nodes[1].starts_with(&[1, 2, 3, 4, 0, 1]));
+    assert!(!nodes[1].ends_with(&[0, 1, 2, 3, 4, 0]));
+    let node = LinkNode::new(7);
+    assert!(node.starts_with(&[7]));
+    assert!(node.ends_with(&[7]));
+    assert!(!node.starts_with(&[7, 7]));
+    assert!(!node.ends_with(&[7, 7]));
+}
+
+#[test]
+fn replace() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    assert_eq!(nodes[2].replace(20), 2);
+    assert_eq!(*nodes[2], 20);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 20, 3, 4]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 4, 3, 20, 1]);
+    assert_eq!(nodes[2].peek_prev(|&i| i), Some(1));
+    assert_eq!(nodes[2].peek_next(|&i| i), Some(3));
+
+    let mut node = LinkNode::new(String::from("a"));
+    assert_eq!(node.replace(String::from("b")), "a");
+    assert!(node.is_standalone());
+    assert_eq!(node.into_inner(), "b");
+}
+
+#[test]
+fn cmp_rings() {
+    let ring = |values: &[i32]| {
+        let mut nodes = values
+            .iter()
+            .copied()
+            .map(LinkNode::new)
+            .collect::<Vec<_>>();
+        let len = nodes.len();
+        connect_all(&mut nodes, 0, len);
+        nodes
+    };
+    let check = |a: &[i32], b: &[i32]| {
+        let (x, y) = (ring(a), ring(b));
+        assert_eq!(x[0].cmp_rings(&y[0]), a.cmp(b), "{a:?} {b:?}");
+        assert_eq!(x[0].partial_cmp_rings(&y[0]), a.partial_cmp(b));
+    };
+    // equal rings
+    check(&[1, 2, 3], &[1, 2, 3]);
+    check(&[5], &[5]);
+    // prefix relations
+    check(&[1, 2], &[1, 2, 3]);
+    check(&[1, 2, 3], &[1, 2]);
+    // differing first element
+    check(&[2, 0, 0], &[1, 9, 9, 9]);
+    check(&[0, 9], &[1]);
+    // differing in the middle
+    check(&[1, 3, 0], &[1, 2, 9]);
+
+    let nodes = ring(&[1, 2, 3]);
+    assert_eq!(nodes[0].cmp_rings(&nodes[0]), Ordering::Equal);
+    assert_eq!(nodes[0].cmp_rings(&nodes[1]), Ordering::Less);
+    assert_eq!(nodes[2].cmp_rings(&nodes[1]), Ordering::Greater);
+
+    let mut floats = [1.0, f64::NAN].map(LinkNode::new);
+    connect_all(&mut floats, 0, 2);
+    let other = LinkNode::new(1.0);
+    assert_eq!(floats[0].partial_cmp_rings(&floats[0]), None);
+    assert_eq!(floats[0].partial_cmp_rings(&other), Some(Ordering::Greater));
+}
+
+#[test]
+fn rotate() {
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let summary = |node: NodeRef<'_, i32>| (*node.data(), node.index(), node.id());
+
+    assert_eq!(nodes[0].rotate_forward(2, summary), (2, 2, nodes[2].id()));
+    assert_eq!(nodes[0].rotate_forward(7, summary), (2, 2, nodes[2].id()));
+    assert_eq!(nodes[3].rotate_forward(2, |node| *node.data()), 0);
+
+    assert_eq!(nodes[0].rotate_backward(2, summary), (3, 3, nodes[3].id()));
+    assert_eq!(nodes[0].rotate_backward(7, summary), (3, 3, nodes[3].id()));
+    assert_eq!(nodes[1].rotate_backward(2, |node| *node.data()), 4);
+
+    assert!(nodes[4].rotate_forward(0, |node| node.is_start()));
+    assert!(nodes[4].rotate_forward(5, |node| node.is_start()));
+    assert!(nodes[4].rotate_backward(10, |node| node.is_start()));
+    assert_eq!(nodes[4].rotate_backward(0, |node| node.index()), 0);
+
+    let node = LinkNode::new(9);
+    assert!(node.rotate_forward(3, |node| node.is_start()));
+    assert!(node.rotate_backward(3, |node| node.is_start()));
+}
+
+#[test]
+fn hash_ring() {
+    let ring = |values: &[i32]| {
+        let mut nodes = values
+            .iter()
+            .copied()
+            .map(LinkNode::new)
+            .collect::<Vec<_>>();
+        let len = nodes.len();
+        connect_all(&mut nodes, 0, len);
+        nodes
+    };
+    let anchored = |node: &LinkNode<i32>| {
+        let mut hasher = DefaultHasher::new();
+        node.hash_ring(&mut hasher);
+        hasher.finish()
+    };
+    let canonical = |node: &LinkNode<i32>| {
+        let mut hasher = DefaultHasher::new();
+        node.hash_ring_canonical(&mut hasher);
+        hasher.finish()
+    };
+    let slice = |values: &[i32]| {
+        let mut hasher = DefaultHasher::new();
+        values.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    // equal rings hash equal, like slices
+    let a = ring(&[3, 1, 4, 1, 5]);
+    let b = ring(&[3, 1, 4, 1, 5]);
+    assert_eq!(anchored(&a[0]), anchored(&b[0]));
+    assert_eq!(anchored(&a[0]), slice(&[3, 1, 4, 1, 5]));
+    assert_eq!(canonical(&a[0]), canonical(&b[0]));
+
+    // rotations change the anchored hash only
+    for i in 1..5 {
+        assert_ne!(anchored(&a[0]), anchored(&a[i]));
+        assert_eq!(canonical(&a[0]), canonical(&a[i]));
+        assert_eq!(canonical(&a[i]), slice(&[1, 4, 1, 5, 3]));
+    }
+
+    // one element difference changes both
+    let c = ring(&[3, 1, 4, 2, 5]);
+    assert_ne!(anchored(&a[0]), anchored(&c[0]));
+    assert_ne!(canonical(&a[0]), canonical(&c[0]));
+
+    // periodic rings and repeated minimums
+    let d = ring(&[1, 2, 1, 2]);
+    assert_eq!(canonical(&d[1]), slice(&[1, 2, 1, 2]));
+    let e = ring(&[1, 1, 0, 1, 0, 0]);
+    for node in &e {
+        assert_eq!(canonical(node), slice(&[0, 0, 1, 1, 0, 1]));
+    }
+    let f = ring(&[7]);
+    assert_eq!(canonical(&f[0]), slice(&[7]));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn duplicates() {
+    let ring = |values: &[i32]| {
+        let mut nodes = values
+            .iter()
+            .copied()
+            .map(LinkNode::new)
+            .collect::<Vec<_>>();
+        let len = nodes.len();
+        connect_all(&mut nodes, 0, len);
+        nodes
+    };
+    let eq = |a: &i32, b: &i32| a == b;
+    let addr = |data: &i32| data as *const i32;
+
+    // all unique
+    let nodes = ring(&[3, 1, 4, 5, 9]);
+    assert!(!nodes[0].has_duplicates());
+    assert_eq!(nodes[2].find_duplicate(|&i| i), None);
+    assert!(!nodes[0].has_duplicates_by(eq));
+    assert!(!LinkNode::new(0).has_duplicates());
+
+    // adjacent duplicates
+    let nodes = ring(&[3, 1, 1, 5]);
+    assert!(nodes[0].has_duplicates());
+    assert_eq!(nodes[0].find_duplicate(|&i| i), Some(1));
+    assert_eq!(nodes[0].find_duplicate(addr), Some(addr(&nodes[2])));
+    assert!(nodes[3].has_duplicates_by(eq));
+
+    // duplicates separated by the wrap-around boundary
+    let nodes = ring(&[7, 1, 2, 7]);
+    assert!(nodes[1].has_duplicates());
+    assert_eq!(nodes[1].find_duplicate(addr), Some(addr(&nodes[0])));
+    assert_eq!(nodes[0].find_duplicate(addr), Some(addr(&nodes[3])));
+    assert!(nodes[2].has_duplicates_by(eq));
+    assert!(!nodes[2].has_duplicates_by(|a, b| a + b == 100));
+    assert!(nodes[2].has_duplicates_by(|a, b| a + b == 3));
+}
+
+#[test]
+fn swap() {
+    fn swap(nodes: &mut [LinkNode<i32>], i: usize, j: usize) {
+        let (a, b) = if i < j {
+            let (head, tail) = nodes.split_at_mut(j);
+            (&mut head[i], &mut tail[0])
+        } else {
+            let (head, tail) = nodes.split_at_mut(i);
+            (&mut tail[0], &mut head[j])
+        };
+        a.swap(b);
+    }
+
+    // non-adjacent
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 6);
+    swap(&mut nodes, 1, 4);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 2, 3, 1, 5]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 5, 1, 3, 2, 4]);
+    swap(&mut nodes, 4, 1);
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 2, 3, 4, 5]);
+    swap(&mut nodes, 0, 3);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 5, 3, 1, 2]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 2, 1, 3, 5, 4]);
+
+    // adjacent, in both orders, and across the wrap-around
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    swap(&mut nodes, 1, 2);
+    assert_eq!(collect(&nodes[0]), vec![0, 2, 1, 3, 4]);
+    swap(&mut nodes, 3, 1);
+    assert_eq!(collect(&nodes[0]), vec![0, 2, 3, 1, 4]);
+    swap(&mut nodes, 4, 0);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 2, 3, 1]);
+    assert_eq!(collect(&nodes[4]), vec![4, 2, 3, 1, 0]);
+    assert_eq!(collect_rev(&nodes[4]), vec![4, 0, 1, 3, 2]);
+
+    // a list of two
+    let mut nodes = (0..2).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 2);
+    swap(&mut nodes, 0, 1);
+    assert_eq!(collect(&nodes[0]), vec![0, 1]);
+    assert_eq!(collect_rev(&nodes[1]), vec![1, 0]);
+
+    // different lists, and standalone nodes
+    let mut nodes = (0..6).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 3);
+    connect_all(&mut nodes, 3, 5);
+    swap(&mut nodes, 1, 4);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 2]);
+    assert_eq!(collect(&nodes[3]), vec![3, 1]);
+    assert_eq!(collect_rev(&nodes[3]), vec![3, 1]);
+    swap(&mut nodes, 2, 5);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 5]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 5, 4]);
+    assert!(nodes[2].is_standalone());
+    swap(&mut nodes, 2, 5);
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 2]);
+    assert!(nodes[5].is_standalone());
+    let mut node = LinkNode::new(9);
+    nodes[5].swap(&mut node);
+    assert!(nodes[5].is_standalone());
+    assert!(node.is_standalone());
+}
+
+#[test]
+fn select_nth_by() {
+    let values = [7, 3, 9, 1, 5, 3, 8];
+    let mut nodes = values.map(LinkNode::new);
+    connect_all(&mut nodes, 0, 7);
+    let mut sorted = values;
+    sorted.sort();
+
+    let copy = |&i: &i32| i;
+    assert_eq!(nodes[2].select_nth_by(0, |a, b| a.cmp(b), copy), Some(1));
+    assert_eq!(nodes[2].select_nth_by(6, |a, b| a.cmp(b), copy), Some(9));
+    for (k, &value) in sorted.iter().enumerate() {
+        assert_eq!(
+            nodes[0].select_nth_by(k, |a, b| a.cmp(b), copy),
+            Some(value)
+        );
+        assert_eq!(
+            nodes[4].select_nth_by_key(k, |&i| -i, copy),
+            Some(sorted[6 - k])
+        );
+    }
+    assert_eq!(nodes[0].select_nth_by(7, |a, b| a.cmp(b), copy), None);
+    assert_eq!(nodes[0].select_nth_by_key(usize::MAX, |&i| i, copy), None);
+
+    // the list is untouched
+    assert_eq!(collect(&nodes[0]), values);
+    assert_eq!(collect_rev(&nodes[0]), vec![7, 8, 3, 5, 1, 9, 3]);
+
+    let node = LinkNode::new(0);
+    assert_eq!(node.select_nth_by_key(0, |&i| i, copy), Some(0));
+    assert_eq!(node.select_nth_by_key(1, |&i| i, copy), None);
+}
+
+#[test]
+fn sort_by() {
+    let mut nodes = [5, 3, 1, 4, 2].map(LinkNode::new);
+    connect_all(&mut nodes, 0, 5);
+    nodes[0].sort_by(|a, b| a.cmp(b));
+    assert_eq!(collect(&nodes[2]), vec![1, 2, 3, 4, 5]);
+    assert_eq!(collect_rev(&nodes[2]), vec![1, 5, 4, 3, 2]);
+    assert_eq!(collect(&nodes[0]), vec![5, 1, 2, 3, 4]);
+    nodes[3].sort_by(|a, b| b.cmp(a));
+    assert_eq!(collect(&nodes[0]), vec![5, 4, 3, 2, 1]);
+    assert_eq!(collect_rev(&nodes[0]), vec![5, 1, 2, 3, 4]);
+
+    // stable, with ties in the order read from the sorted node
+    let mut pairs = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd'), (0, 'e'), (2, 'f')].map(LinkNode::new);
+    connect_all(&mut pairs, 0, 6);
+    pairs[2].sort_by(|a, b| a.0.cmp(&b.0));
+    let order = unsafe { pairs[4].iter() }
+        .map(|&(_, c)| c)
+        .collect::<String>();
+    assert_eq!(order, "edbcfa");
+
+    // many sizes and orders, against the sort of a vector
+    for len in 1..40 {
+        let values = (0..len).map(|i| (i * 7919) % 13).collect::<Vec<_>>();
+        let mut nodes = values
+            .iter()
+            .copied()
+            .map(LinkNode::new)
+            .collect::<Vec<_>>();
+        connect_all(&mut nodes, 0, len);
+        nodes[0].sort_by(|a, b| a.cmp(b));
+        assert!(nodes[0].is_sorted_circular());
+        let mut read = collect(&nodes[0]);
+        let mut rev = collect_rev(&nodes[0]);
+        rev[1..].reverse();
+        assert_eq!(rev, read);
+        let mut sorted = values.clone();
+        sorted.sort();
+        read.sort();
+        assert_eq!(read, sorted);
+    }
+
+    let mut node = LinkNode::new(0);
+    node.sort_by(|a, b| a.cmp(b));
+    assert!(node.is_standalone());
+}
+
+#[test]
+fn sort_by_panic() {
+    // a comparison panicking at any point leaves a consistent ring
+    for len in [2, 3, 5, 8, 13, 21] {
+        let values = (0..len).map(|i| (i * 7919) % 23).collect::<Vec<_>>();
+        let mut panic_at = 0;
+        loop {
+            let mut nodes = values
+                .iter()
+                .copied()
+                .map(LinkNode::new)
+                .collect::<Vec<_>>();
+            connect_all(&mut nodes, 0, len);
+            let mut compared = 0;
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                nodes[0].sort_by(|a, b| {
+                    assert_ne!(compared, panic_at, "comparison failed");
+                    compared += 1;
+                    a.cmp(b)
+                })
+            }));
+            let mut read = collect(&nodes[0]);
+            let mut rev = collect_rev(&nodes[0]);
+            rev[1..].reverse();
+            assert_eq!(rev, read);
+            assert!(nodes.iter().all(|node| node.same_list(&nodes[0])));
+            let mut sorted = values.clone();
+            sorted.sort();
+            read.sort();
+            assert_eq!(read, sorted);
+            if result.is_ok() {
+                assert!(nodes[0].is_sorted_circular());
+                break;
+            }
+            panic_at += 1;
+        }
+        assert!(panic_at > 0);
+    }
+}
+
+#[test]
+fn dedup_by() {
+    let mut nodes = [1, 1, 2, 2, 2, 3].map(LinkNode::new);
+    connect_all(&mut nodes, 0, 6);
+    nodes[0].dedup_by(|a, b| a == b);
+    assert_eq!(collect(&nodes[0]), vec![1, 2, 3]);
+    assert_eq!(collect_rev(&nodes[0]), vec![1, 3, 2]);
+    for i in [1, 3, 4] {
+        assert!(nodes[i].is_standalone());
+    }
+
+    // the wrap-around pair is not compared, and `self` is kept
+    let mut nodes = [1, 2, 2, 1, 1].map(LinkNode::new);
+    connect_all(&mut nodes, 0, 5);
+    nodes[2].dedup_by(|a, b| a == b);
+    assert_eq!(collect(&nodes[2]), vec![2, 1, 2]);
+    assert!(nodes[4].is_standalone() && nodes[0].is_standalone());
+
+    // compared with the last element kept, not the previous one
+    let mut nodes = [0, 1, 2, 3, 4, 5].map(LinkNode::new);
+    connect_all(&mut nodes, 0, 6);
+    nodes[0].dedup_by(|a, b| a - b < 2);
+    assert_eq!(collect(&nodes[0]), vec![0, 2, 4]);
+
+    let mut list = List::new();
+    list.extend([1, 1, 2, 2, 2, 3]);
+    list.dedup_by(|a, b| a == b);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.pop_back(), Some(3));
+
+    let dropped = Rc::new(());
+    let mut list = List::new();
+    list.extend((0..6).map(|i| (i / 3, dropped.clone())));
+    list.dedup_by(|a, b| a.0 == b.0);
+    assert_eq!(list.len(), 2);
+    assert_eq!(Rc::strong_count(&dropped), 3);
+    let mut empty = List::<i32>::new();
+    empty.dedup_by(|_, _| true);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn retain() {
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    let mut visited = vec![];
+    nodes[0].retain(|&i| {
+        visited.push(i);
+        i % 2 == 0
+    });
+    assert_eq!(visited, (0..10).collect::<Vec<_>>());
+    assert_eq!(collect(&nodes[0]), vec![0, 2, 4, 6, 8]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 8, 6, 4, 2]);
+    assert!(nodes.iter().skip(1).step_by(2).all(|n| n.is_standalone()));
+
+    // removing the anchor
+    let mut nodes = (0..10).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 10);
+    nodes[3].retain(|&i| i % 2 == 0);
+    assert!(nodes[3].is_standalone());
+    assert_eq!(collect(&nodes[4]), vec![4, 6, 8, 0, 2]);
+    assert_eq!(collect_rev(&nodes[4]), vec![4, 2, 0, 8, 6]);
+    nodes[4].retain(|_| false);
+    assert!(nodes.iter().all(|n| n.is_standalone()));
+
+    let mut list = List::new();
+    list.extend(0..10);
+    list.retain(|&i| i % 2 == 0);
+    assert_eq!(list.len(), 5);
+    assert_eq!(
+        list.iter().copied().collect::<Vec<_>>(),
+        vec![0, 2, 4, 6, 8]
+    );
+    assert_eq!(list.pop_back(), Some(8));
+    let dropped = Rc::new(());
+    let mut list = List::new();
+    list.extend((0..4).map(|i| (i, dropped.clone())));
+    list.retain(|_| false);
+    assert!(list.is_empty());
+    assert_eq!(Rc::strong_count(&dropped), 1);
+}
+
+#[test]
+fn list_extract_if() {
+    let mut list = List::new();
+    list.extend(0..6);
+    let mut extracted = list.extract_if(|i| *i % 2 == 1).collect::<Vec<_>>();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![0, 2, 4]);
+    assert_eq!(
+        list.iter().rev().copied().collect::<Vec<_>>(),
+        vec![4, 2, 0]
+    );
+    assert!(extracted.iter().all(|n| n.is_standalone()));
+    assert_eq!(extracted.iter().map(|n| **n).collect::<Vec<_>>(), [1, 3, 5]);
+
+    // the extracted nodes are owned, and can be linked again
+    let (first, rest) = extracted.split_first_mut().unwrap();
+    first.add(&mut rest[0]);
+    assert_eq!(collect(first), vec![1, 3]);
+
+    // stopping early leaves the rest in the list
+    let mut iter = list.extract_if(|i| {
+        *i += 1;
+        true
+    });
+    assert_eq!(iter.next().map(LinkNode::into_inner), Some(1));
+    drop(iter);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![2, 4]);
+    assert_eq!(list.pop_back(), Some(4));
+    assert_eq!(list.pop_back(), Some(2));
+    assert_eq!(list.extract_if(|_| true).count(), 0);
+}
+
+#[test]
+fn list_drain() {
+    let mut list = List::new();
+    list.extend(0..4);
+    let mut drain = list.drain();
+    assert_eq!(drain.len(), 4);
+    let mut first = drain.next().unwrap();
+    assert!(first.is_standalone());
+    assert_eq!(drain.len(), 3);
+    let mut rest = drain.collect::<Vec<_>>();
+    assert!(list.is_empty());
+    assert_eq!(list.iter().count(), 0);
+    assert!(rest.iter().all(|n| n.is_standalone()));
+    assert_eq!(rest.iter().map(|n| **n).collect::<Vec<_>>(), [1, 2, 3]);
+    for node in rest.iter_mut().rev() {
+        first.add(node);
+    }
+    assert_eq!(collect(&first), vec![0, 1, 2, 3]);
+
+    // dropping early drops the remaining elements
+    let dropped = Rc::new(());
+    let mut list = List::new();
+    list.extend((0..4).map(|i| (i, dropped.clone())));
+    let mut drain = list.drain();
+    let node = drain.next().unwrap();
+    drop(drain);
+    assert!(list.is_empty());
+    assert_eq!(Rc::strong_count(&dropped), 2);
+    assert_eq!(node.into_inner().0, 0);
+    assert_eq!(Rc::strong_count(&dropped), 1);
+    list.push_back((4, dropped.clone()));
+    assert_eq!(list.pop_front().map(|(i, _)| i), Some(4));
+}
+
+#[test]
+fn list_from_iter() {
+    let list: List<i32> = (0..10).collect();
+    assert_eq!(list.len(), 10);
+    assert_eq!(
+        list.iter().copied().collect::<Vec<_>>(),
+        (0..10).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        list.iter().rev().copied().collect::<Vec<_>>(),
+        (0..10).rev().collect::<Vec<_>>()
+    );
+    assert!(std::iter::empty::<i32>().collect::<List<_>>().is_empty());
+}
+
+#[test]
+fn replace_with() {
+    // in the middle of a list, with `other` from another list
+    let mut nodes = (0..7).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    connect_all(&mut nodes, 5, 7);
+    let (n0, n1) = nodes.split_at_mut(5);
+    n0[2].replace_with(&mut n1[0]);
+    assert!(nodes[2].is_standalone());
+    assert_eq!(collect(&nodes[0]), vec![0, 1, 5, 3, 4]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 4, 3, 5, 1]);
+    assert!(nodes[6].is_standalone());
+
+    // a standalone node
+    let mut n0 = LinkNode::new(0);
+    let mut n1 = LinkNode::new(1);
+    n0.replace_with(&mut n1);
+    assert!(n0.is_standalone());
+    assert!(n1.is_standalone());
+
+    // `other` in the same list, adjacent or not
+    let mut nodes = (0..5).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 5);
+    let (n0, n1) = nodes.split_at_mut(3);
+    n0[1].replace_with(&mut n1[1]);
+    assert!(nodes[1].is_standalone());
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 2, 3]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 3, 2, 4]);
+    let (n0, n1) = nodes.split_at_mut(3);
+    n0[2].replace_with(&mut n1[0]);
+    assert!(nodes[2].is_standalone());
+    assert_eq!(collect(&nodes[0]), vec![0, 4, 3]);
+    assert_eq!(collect_rev(&nodes[0]), vec![0, 3, 4]);
+}
+
+#[test]
+fn list_linked_list() {
+    use std::collections::LinkedList;
+
+    let linked = LinkedList::from([1, 2, 3]);
+    let list = List::from(linked.clone());
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(
+        list.iter().rev().copied().collect::<Vec<_>>(),
+        vec![3, 2, 1]
+    );
+    assert_eq!(LinkedList::from(list), linked);
+
+    let list = List::from(LinkedList::<i32>::new());
+    assert!(list.is_empty());
+    assert!(LinkedList::from(list).is_empty());
+}
+
+#[test]
+fn list_len_count() {
+    let mut list = List::new();
+    let check = |list: &List<i32>| assert_eq!(list.len(), list.count());
+    check(&list);
+    assert_eq!(list.count(), 0);
+    list.extend(0..10);
+    list.push_front(-1);
+    list.push_back(10);
+    check(&list);
+    list.pop_front();
+    list.pop_back();
+    check(&list);
+    list.retain(|&i| i != 4);
+    check(&list);
+    list.extend([9, 9]);
+    list.dedup_by(|a, b| a == b);
+    check(&list);
+    let odd = list.extract_if(|i| *i % 2 == 1).take(2).count();
+    assert_eq!(odd, 2);
+    check(&list);
+    assert_eq!(list.count(), 7);
+    list.extend((20..22).map(LinkNode::new));
+    check(&list);
+    drop(list.drain().next());
+    check(&list);
+    assert_eq!(list.count(), 0);
+}
+
 // helper functions
 
 fn collect<T: Copy>(node: &LinkNode<T>) -> Vec<T> {
@@ -0,0 +1,49 @@
+#![cfg(feature = "debug-guard")]
+
+use cdlist::LinkNode;
+use std::cell::RefCell;
+
+#[test]
+#[should_panic(expected = "structurally modified")]
+fn take_sibling_in_for_each() {
+    let mut node0 = LinkNode::new(0);
+    let sibling = RefCell::new(LinkNode::new(1));
+    node0.add(&mut sibling.borrow_mut());
+    node0.for_each(|_| sibling.borrow_mut().take());
+}
+
+#[test]
+#[should_panic(expected = "structurally modified")]
+fn add_sibling_in_for_each_rev() {
+    let mut node0 = LinkNode::new(0);
+    let node1 = RefCell::new(LinkNode::new(1));
+    let node2 = RefCell::new(LinkNode::new(2));
+    node0.add(&mut node1.borrow_mut());
+    node0.for_each_rev(|&i| {
+        if i == 1 {
+            node1.borrow_mut().add(&mut node2.borrow_mut());
+        }
+    });
+}
+
+#[test]
+#[should_panic(expected = "structurally modified")]
+fn take_sibling_in_iterator() {
+    let mut node0 = LinkNode::new(0);
+    let sibling = RefCell::new(LinkNode::new(1));
+    node0.add(&mut sibling.borrow_mut());
+    for _ in unsafe { node0.iter() } {
+        sibling.borrow_mut().take();
+    }
+}
+
+#[test]
+fn modify_other_list() {
+    let mut node0 = LinkNode::new(0);
+    let mut node1 = LinkNode::new(1);
+    node0.add(&mut node1);
+    let other0 = RefCell::new(LinkNode::new(2));
+    let other1 = RefCell::new(LinkNode::new(3));
+    node0.for_each(|_| other0.borrow_mut().add(&mut other1.borrow_mut()));
+    assert_eq!(node0.len(), 2);
+}
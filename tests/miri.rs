@@ -0,0 +1,196 @@
+//! Small tests covering every pointer operation of the crate,
+//! meant to be run under Miri to catch undefined behavior:
+//!
+//! ```text
+//! cargo +nightly miri test --test miri
+//! MIRIFLAGS=-Zmiri-strict-provenance cargo +nightly miri test --test miri
+//! ```
+//!
+//! They also run as plain tests, and are kept short so that
+//! the interpreter finishes them in a few seconds.
+use cdlist::{LinkNode, List};
+use std::{cell::RefCell, rc::Rc};
+
+/// Pushes its value into a shared log when dropped.
+struct DropLog(u32, Rc<RefCell<Vec<u32>>>);
+
+impl Drop for DropLog {
+    fn drop(&mut self) {
+        self.1.borrow_mut().push(self.0);
+    }
+}
+
+fn connect_all<T>(nodes: &mut [LinkNode<T>], start: usize, end: usize) {
+    for i in start..(end - 1) {
+        let (ni, nj) = nodes[i..].split_at_mut(1);
+        ni[0].add(&mut nj[0])
+    }
+}
+
+fn ring(len: usize) -> Vec<LinkNode<i32>> {
+    let mut nodes = (0..len as i32).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, len);
+    nodes
+}
+
+fn values(node: &LinkNode<i32>) -> Vec<i32> {
+    unsafe { node.iter() }.copied().collect()
+}
+
+#[test]
+fn construction() {
+    let node = LinkNode::new(1);
+    assert!(node.is_standalone());
+    assert_eq!(*node, 1);
+
+    let node = LinkNode::try_new(String::from("a")).unwrap();
+    assert_eq!(node.as_str(), "a");
+    assert_eq!(node.into_inner(), "a");
+
+    let mut node = LinkNode::new(vec![1]);
+    node.push(2);
+    assert_eq!(node.into_inner(), [1, 2]);
+}
+
+#[test]
+fn linking() {
+    let mut nodes = ring(4);
+    assert_eq!(values(&nodes[0]), [0, 1, 2, 3]);
+    assert_eq!(nodes[2].peek_next(|&i| i), Some(3));
+    assert_eq!(nodes[2].peek_prev(|&i| i), Some(1));
+
+    let mut extra = ring(2);
+    nodes[1].append(&mut extra[0]);
+    assert_eq!(values(&nodes[0]), [0, 1, 0, 1, 2, 3]);
+
+    let (head, tail) = nodes.split_at_mut(2);
+    tail[0].split_off(&mut head[0]);
+    assert_eq!(values(&nodes[0]), [0, 1, 0, 1]);
+    assert_eq!(values(&nodes[2]), [2, 3]);
+
+    nodes[0].reverse();
+    assert_eq!(values(&nodes[0]), [0, 1, 0, 1]);
+    assert_eq!(values(&extra[1]), [1, 0, 1, 0]);
+}
+
+#[test]
+fn requeue() {
+    let mut a = ring(3);
+    let mut b = ring(3);
+    a[0].add(&mut b[1]);
+    assert_eq!(values(&a[0]), [0, 1, 1, 2]);
+    assert_eq!(values(&b[0]), [0, 2]);
+
+    b[2].add_before(&mut a[1]);
+    assert_eq!(values(&a[0]), [0, 1, 2]);
+    assert_eq!(values(&b[0]), [0, 1, 2]);
+
+    // moving a node to its own neighbor is a no-op on the order
+    let (head, tail) = a.split_at_mut(1);
+    head[0].add_before(&mut tail[1]);
+    assert_eq!(values(&a[0]), [0, 1, 2]);
+}
+
+#[test]
+fn take() {
+    let mut nodes = ring(3);
+    nodes[1].take();
+    assert!(nodes[1].is_standalone());
+    assert_eq!(values(&nodes[0]), [0, 2]);
+    nodes[1].take();
+    assert!(nodes[1].is_standalone());
+
+    nodes[0].take();
+    assert!(nodes[2].is_standalone());
+    assert_eq!(nodes.pop().unwrap().into_inner(), 2);
+}
+
+#[test]
+fn drop_order() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut nodes = (0..4)
+        .map(|i| LinkNode::new(DropLog(i, log.clone())))
+        .collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 4);
+
+    // drop from the middle, then the anchor, then the rest
+    drop(nodes.remove(2));
+    drop(nodes.remove(0));
+    assert_eq!(nodes[0].len(), 2);
+    assert_eq!(nodes[1].peek_next(|d| d.0), Some(1));
+    drop(nodes);
+    assert_eq!(*log.borrow(), [2, 0, 1, 3]);
+}
+
+#[test]
+fn for_each_variants() {
+    let mut nodes = ring(4);
+
+    let mut seen = vec![];
+    nodes[1].for_each(|&i| seen.push(i));
+    assert_eq!(seen, [1, 2, 3, 0]);
+
+    seen.clear();
+    nodes[1].for_each_rev(|&i| seen.push(i));
+    assert_eq!(seen, [1, 0, 3, 2]);
+
+    nodes[1].for_each_mut(|i| *i *= 10);
+    assert_eq!(values(&nodes[0]), [0, 10, 20, 30]);
+
+    let mut next = 0;
+    nodes[1].for_each_mut_rev(|i| {
+        *i = next;
+        next += 1;
+    });
+    assert_eq!(values(&nodes[1]), [0, 3, 2, 1]);
+}
+
+#[test]
+fn iterators() {
+    let mut nodes = ring(4);
+
+    let mut iter = unsafe { nodes[0].iter() };
+    assert_eq!(iter.next_back(), Some(&3));
+    assert_eq!(iter.next(), Some(&0));
+    assert_eq!(iter.collect::<Vec<_>>(), [&1, &2]);
+
+    let mut iter = unsafe { nodes[0].iter_mut() };
+    let front = iter.next().unwrap();
+    let back = iter.next_back().unwrap();
+    std::mem::swap(front, back);
+    assert_eq!(values(&nodes[0]), [3, 1, 2, 0]);
+
+    let cycle = unsafe { nodes[2].cycle_iter() }
+        .take(6)
+        .copied()
+        .collect::<Vec<_>>();
+    assert_eq!(cycle, [2, 0, 3, 1, 2, 0]);
+}
+
+#[test]
+fn cursor() {
+    let mut nodes = ring(4);
+    let mut extra = LinkNode::new(9);
+    let mut cursor = unsafe { nodes[0].cursor_mut() };
+    cursor.move_next();
+    assert!(cursor.remove_current());
+    cursor.insert_after(&mut extra);
+    assert_eq!(cursor.current(), Some(&mut 2));
+    assert_eq!(values(&nodes[0]), [0, 2, 9, 3]);
+    assert!(nodes[1].is_standalone());
+}
+
+#[test]
+fn owned_list() {
+    let log = Rc::new(RefCell::new(vec![]));
+    let mut list = List::new();
+    for i in 0..3 {
+        list.push_back(DropLog(i, log.clone()));
+    }
+    list.push_front(DropLog(3, log.clone()));
+    assert_eq!(list.pop_back().map(|d| d.0), Some(2));
+    assert_eq!(list.iter().map(|d| d.0).collect::<Vec<_>>(), [3, 0, 1]);
+    list.iter_mut().for_each(|d| d.0 += 10);
+    drop(list);
+    assert_eq!(*log.borrow(), [2, 13, 10, 11]);
+}
@@ -0,0 +1,35 @@
+//! Exercises the crate built with `--no-default-features`,
+//! where it only links `core` and `alloc`.
+#![cfg(not(feature = "std"))]
+
+use cdlist::{Collector, LinkNode};
+
+#[test]
+fn no_std_build() {
+    let mut nodes = (0..4).map(LinkNode::new).collect::<Vec<_>>();
+    connect_all(&mut nodes, 0, 4);
+    assert_eq!(nodes[0].to_vec(), [0, 1, 2, 3]);
+    assert_eq!(
+        unsafe { nodes[0].iter() }
+            .rev()
+            .copied()
+            .collect::<Vec<_>>(),
+        [3, 2, 1, 0]
+    );
+
+    let mut collector = Collector::new();
+    assert!(nodes[0].accept(&mut collector).is_continue());
+    assert_eq!(collector.into_items(), [0, 1, 2, 3]);
+
+    nodes[2].take();
+    assert_eq!(nodes[0].to_vec(), [0, 1, 3]);
+}
+
+// helper functions
+
+fn connect_all<T>(nodes: &mut [LinkNode<T>], start: usize, end: usize) {
+    for i in start..(end - 1) {
+        let (ni, nj) = nodes[i..].split_at_mut(1);
+        ni[0].add(&mut nj[0])
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "serde")]
+
+use cdlist::{LinkNode, List};
+
+#[test]
+fn serialize_link_node() {
+    let mut node1 = LinkNode::new(1);
+    let mut node2 = LinkNode::new(2);
+    let mut node3 = LinkNode::new(3);
+    node1.add(&mut node3);
+    node1.add(&mut node2);
+    assert_eq!(serde_json::to_string(&node1).unwrap(), "[1,2,3]");
+    assert_eq!(serde_json::to_string(&node3).unwrap(), "[3,1,2]");
+    assert_eq!(
+        serde_json::to_string(&LinkNode::new("a")).unwrap(),
+        r#"["a"]"#
+    );
+}
+
+#[test]
+fn round_trip() {
+    let mut node1 = LinkNode::new(1);
+    let mut node2 = LinkNode::new(2);
+    let mut node3 = LinkNode::new(3);
+    node1.add(&mut node3);
+    node1.add(&mut node2);
+    let json = serde_json::to_string(&node1).unwrap();
+
+    let list: List<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(serde_json::to_string(&list).unwrap(), json);
+
+    let empty: List<String> = serde_json::from_str("[]").unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(serde_json::to_string(&empty).unwrap(), "[]");
+
+    assert!(serde_json::from_str::<List<i32>>(r#"[1,"2"]"#).is_err());
+    assert!(serde_json::from_str::<List<i32>>("1").is_err());
+}
+
+#[test]
+fn round_trip_with_length() {
+    // bincode needs the length of a sequence before its elements
+    let mut node1 = LinkNode::new(String::from("a"));
+    let mut node2 = LinkNode::new(String::from("bc"));
+    node1.add(&mut node2);
+    let bytes = bincode::serialize(&node2).unwrap();
+
+    let list: List<String> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(
+        list.iter().map(String::as_str).collect::<Vec<_>>(),
+        ["bc", "a"]
+    );
+    assert_eq!(bincode::serialize(&list).unwrap(), bytes);
+
+    let bytes = bincode::serialize(&LinkNode::new(7u8)).unwrap();
+    let list: List<u8> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), [7]);
+}